@@ -0,0 +1,615 @@
+use chain_poker::{
+    error::ContractError as TableError,
+    msg::{BettingStructure, BlindLevelDuration, GameVariant, TournamentConfig},
+    poker::tournament::{finishing_order, share_prize_pool, validate_config},
+    state::{is_valid_username, PreStartState, MAX_SEATS},
+};
+use chain_poker_factory::contract::parse_instantiate_address;
+use cosmwasm_std::{
+    to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Reply,
+    Response, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+};
+
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{
+        Config, Player, TableInfo, TableSeating, TournamentState, BLIND_LEVEL, CONFIG, ELIMINATED,
+        PENDING_SEATINGS, PLAYERS, PRIZES, PRIZE_POOL, SEATS, STARTED_AT, STARTING_STACKS, TABLES,
+        WINNER,
+    },
+};
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    validate_config(&msg.tournament)?;
+    if let BlindLevelDuration::Hands(_) = msg.tournament.level_duration {
+        return Err(ContractError::TimeBasedBlindLevelsOnly);
+    }
+
+    let config = Config {
+        admin: info.sender,
+        table_code_id: msg.table_code_id,
+        table_code_hash: msg.table_code_hash,
+        tournament: msg.tournament,
+        turn_time_limit: msg.turn_time_limit,
+    };
+    CONFIG.save(deps.storage, &config)?;
+    PRIZE_POOL.save(deps.storage, &0)?;
+    BLIND_LEVEL.save(deps.storage, &0)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Register { username } => try_register(deps, info, username),
+        ExecuteMsg::Unregister {} => try_unregister(deps, info.sender),
+        ExecuteMsg::StartTournament {} => try_start_tournament(deps, env, info.sender),
+        ExecuteMsg::UpdateTables {} => try_update_tables(deps, env),
+        ExecuteMsg::ClaimPrize {} => try_claim_prize(deps, info.sender),
+    }
+}
+
+fn is_started(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(STARTED_AT.may_load(storage)?.is_some())
+}
+
+fn send_uscrt(recipient: Addr, amount: u128) -> CosmosMsg {
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.into_string(),
+        amount: vec![Coin {
+            denom: "uscrt".to_string(),
+            amount: Uint128::from(amount),
+        }],
+    })
+}
+
+fn table_message(table: &TableInfo, msg: &chain_poker::msg::ExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: table.address.to_string(),
+        code_hash: table.code_hash.clone(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }))
+}
+
+fn try_register(
+    deps: DepsMut,
+    info: MessageInfo,
+    username: String,
+) -> Result<Response, ContractError> {
+    if is_started(deps.storage)? {
+        return Err(TableError::GameAlreadyStarted.into());
+    }
+
+    let buy_in = CONFIG.load(deps.storage)?.tournament.buy_in.u128();
+    let [ref funds] = info.funds[..] else {
+        return Err(TableError::InvalidDenom.into());
+    };
+    if funds.denom != "uscrt" {
+        return Err(TableError::InvalidDenom.into());
+    }
+    if funds.amount.u128() != buy_in {
+        return Err(TableError::TournamentBuyIn { buy_in }.into());
+    }
+
+    if !is_valid_username(&username) {
+        return Err(TableError::InvalidUsername.into());
+    }
+
+    for player in PLAYERS.iter(deps.storage)? {
+        let player = player?;
+        if player.address == info.sender {
+            return Err(TableError::AlreadyRegistered.into());
+        }
+        if player.username.to_lowercase() == username.to_lowercase() {
+            return Err(TableError::UsernameTaken { username }.into());
+        }
+    }
+
+    PLAYERS.push(
+        deps.storage,
+        &Player {
+            address: info.sender,
+            username,
+        },
+    )?;
+    PRIZE_POOL.update(deps.storage, |prize_pool| Ok(prize_pool + buy_in))?;
+
+    Ok(Response::default())
+}
+
+fn try_unregister(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
+    if is_started(deps.storage)? {
+        return Err(TableError::GameAlreadyStarted.into());
+    }
+
+    let position = PLAYERS
+        .iter(deps.storage)?
+        .position(|player| player.is_ok_and(|player| player.address == sender))
+        .ok_or(ContractError::NotRegistered)?;
+    PLAYERS.remove(deps.storage, position as u32)?;
+
+    let buy_in = CONFIG.load(deps.storage)?.tournament.buy_in.u128();
+    PRIZE_POOL.update(deps.storage, |prize_pool| Ok(prize_pool - buy_in))?;
+
+    Ok(Response::new().add_message(send_uscrt(sender, buy_in)))
+}
+
+fn try_start_tournament(deps: DepsMut, env: Env, sender: Addr) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if sender != config.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    if is_started(deps.storage)? {
+        return Err(TableError::GameAlreadyStarted.into());
+    }
+
+    // Every place in the payout table needs a player to finish in it.
+    let players: Vec<Player> = PLAYERS.iter(deps.storage)?.collect::<StdResult<_>>()?;
+    if players.len() < config.tournament.payouts.len().max(2) {
+        return Err(TableError::NotEnoughPlayers.into());
+    }
+
+    STARTED_AT.save(deps.storage, &env.block.time.seconds())?;
+
+    // Players are dealt around the fewest tables that can seat them, so the tables differ in
+    // size by at most one player.
+    let num_tables = players.len().div_ceil(MAX_SEATS as usize);
+    let mut seatings: Vec<Vec<Player>> = vec![vec![]; num_tables];
+    for (index, player) in players.into_iter().enumerate() {
+        seatings[index % num_tables].push(player);
+    }
+
+    let table = chain_poker::msg::InstantiateMsg {
+        // The director is not a player, so it does not need a username.
+        username: String::new(),
+        big_blind: config.tournament.blind_levels[0],
+        max_buy_in_bb: 0,
+        min_buy_in_bb: 0,
+        turn_time_limit: config.turn_time_limit,
+        admin: None,
+        tournament: None,
+        director: Some(env.contract.address.to_string()),
+        ante: None,
+        allow_straddle: false,
+        betting_structure: BettingStructure::NoLimit,
+        variant: GameVariant::Holdem,
+        disable_spectators: false,
+    };
+
+    let mut response = Response::new();
+    for (index, seating) in seatings.iter().enumerate() {
+        let reply_id = index as u64;
+        PENDING_SEATINGS.insert(deps.storage, &reply_id, seating)?;
+
+        let instantiate_message = WasmMsg::Instantiate {
+            admin: None,
+            code_id: config.table_code_id,
+            code_hash: config.table_code_hash.clone(),
+            msg: to_binary(&table)?,
+            funds: vec![],
+            label: format!("chain-poker-tournament-{}-{index}", env.contract.address),
+        };
+        response = response.add_submessage(SubMsg::reply_on_success(instantiate_message, reply_id));
+    }
+
+    Ok(response)
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let Some(seating) = PENDING_SEATINGS.get(deps.storage, &msg.id) else {
+        return Err(ContractError::UnexpectedReply { id: msg.id });
+    };
+    PENDING_SEATINGS.remove(deps.storage, &msg.id)?;
+
+    let response = msg
+        .result
+        .into_result()
+        .map_err(|reason| ContractError::TableInstantiationFailed { reason })?;
+    let address = response
+        .data
+        .and_then(|data| parse_instantiate_address(data.as_slice()))
+        .ok_or_else(|| ContractError::TableInstantiationFailed {
+            reason: "The reply did not contain the table's address".to_string(),
+        })?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let table = TableInfo {
+        address: deps.api.addr_validate(&address)?,
+        code_hash: config.table_code_hash,
+        is_closed: false,
+    };
+    let table_index = TABLES.get_len(deps.storage)?;
+    TABLES.push(deps.storage, &table)?;
+
+    let mut messages = Vec::new();
+    for player in seating {
+        SEATS.insert(deps.storage, &player.address, &table_index)?;
+        STARTING_STACKS.insert(
+            deps.storage,
+            &player.address,
+            &config.tournament.starting_stack.into(),
+        )?;
+        messages.push(table_message(
+            &table,
+            &chain_poker::msg::ExecuteMsg::SeatPlayer {
+                address: player.address.to_string(),
+                username: player.username,
+                stack: config.tournament.starting_stack.into(),
+            },
+        )?);
+    }
+    messages.push(table_message(
+        &table,
+        &chain_poker::msg::ExecuteMsg::StartGame {},
+    )?);
+
+    Ok(Response::new().add_messages(messages))
+}
+
+/// A table still in use, along with the players seated at it.
+struct Seating {
+    index: u32,
+    table: TableInfo,
+    /// Whether a hand is being played at the table. Otherwise it is waiting for the director to
+    /// deal the next hand.
+    is_started: bool,
+    /// Each player's stack, and whether they can be moved without interrupting a hand.
+    players: Vec<(Player, u128, bool)>,
+}
+
+/// Look up the stacks of the players at every table still in use, eliminating those who have no
+/// chips left and are not waiting on the result of a hand. Players knocked out at different
+/// tables are eliminated in order of the chips they started their last hand with, as they are at
+/// a single table.
+fn load_seatings(deps: &mut DepsMut) -> Result<Vec<Seating>, ContractError> {
+    let players: Vec<Player> = PLAYERS.iter(deps.storage)?.collect::<StdResult<_>>()?;
+    let tables: Vec<TableInfo> = TABLES.iter(deps.storage)?.collect::<StdResult<_>>()?;
+
+    let mut seatings = Vec::new();
+    let mut busted_players = Vec::new();
+    for (index, table) in tables.into_iter().enumerate() {
+        if table.is_closed {
+            continue;
+        }
+
+        let table_state: PreStartState = deps.querier.query_wasm_smart(
+            &table.code_hash,
+            table.address.as_str(),
+            &chain_poker::msg::QueryMsg::ViewPreStartState {},
+        )?;
+
+        let mut seated_players = Vec::new();
+        for player in &players {
+            if SEATS.get(deps.storage, &player.address) != Some(index as u32) {
+                continue;
+            }
+
+            let stack = table_state
+                .balances
+                .iter()
+                .find(|(username, _)| *username == player.username)
+                .map_or(0, |(_, balance)| *balance);
+            let is_in_hand = table_state.players_in_hand.contains(&player.username);
+
+            if stack == 0 && !is_in_hand {
+                SEATS.remove(deps.storage, &player.address)?;
+                let starting_stack = STARTING_STACKS
+                    .get(deps.storage, &player.address)
+                    .unwrap_or(0);
+                STARTING_STACKS.remove(deps.storage, &player.address)?;
+                busted_players.push((player.address.clone(), starting_stack));
+            } else {
+                // Between hands, the player's stack is the one they start their next hand with.
+                if !is_in_hand {
+                    STARTING_STACKS.insert(deps.storage, &player.address, &stack)?;
+                }
+                seated_players.push((player.clone(), stack, !is_in_hand));
+            }
+        }
+
+        seatings.push(Seating {
+            index: index as u32,
+            table,
+            is_started: table_state.is_started,
+            players: seated_players,
+        });
+    }
+
+    busted_players.sort_by_key(|(_, starting_stack)| *starting_stack);
+    busted_players
+        .iter()
+        .try_for_each(|(address, _)| ELIMINATED.push(deps.storage, address))?;
+
+    Ok(seatings)
+}
+
+/// Share out the prize pool once only one player has chips left.
+fn finish_tournament(
+    winner: &Player,
+    tournament: &TournamentConfig,
+    storage: &mut dyn Storage,
+) -> StdResult<()> {
+    WINNER.save(storage, &winner.address)?;
+
+    let prizes = share_prize_pool(PRIZE_POOL.load(storage)?, &tournament.payouts);
+    finishing_order(&WINNER, &ELIMINATED, storage)?
+        .iter()
+        .zip(prizes)
+        .try_for_each(|(address, prize)| PRIZES.insert(storage, address, &prize))
+}
+
+/// Move one player from the table at `from` to the table at `to`.
+fn move_player(
+    seatings: &mut [Seating],
+    from: usize,
+    to: usize,
+    player_position: usize,
+    messages: &mut Vec<CosmosMsg>,
+    storage: &mut dyn Storage,
+) -> StdResult<()> {
+    let (player, stack, _) = seatings[from].players.remove(player_position);
+
+    messages.push(table_message(
+        &seatings[from].table,
+        &chain_poker::msg::ExecuteMsg::UnseatPlayer {
+            address: player.address.to_string(),
+        },
+    )?);
+    messages.push(table_message(
+        &seatings[to].table,
+        &chain_poker::msg::ExecuteMsg::SeatPlayer {
+            address: player.address.to_string(),
+            username: player.username.clone(),
+            stack: stack.into(),
+        },
+    )?);
+
+    SEATS.insert(storage, &player.address, &seatings[to].index)?;
+    // A player who has just been moved is not dealt into a hand yet, but they should not be moved
+    // again straight away.
+    seatings[to].players.push((player, stack, false));
+
+    Ok(())
+}
+
+/// Move players so that every table is within one player of the others, and break up a table
+/// whenever the remaining players fit around one fewer table.
+fn balance_tables(
+    seatings: &mut [Seating],
+    messages: &mut Vec<CosmosMsg>,
+    storage: &mut dyn Storage,
+) -> StdResult<()> {
+    let num_players: usize = seatings.iter().map(|seating| seating.players.len()).sum();
+    let num_tables = num_players.div_ceil(MAX_SEATS as usize);
+
+    if seatings.len() > num_tables {
+        // The smallest table is broken up, its players filling the emptiest seats elsewhere.
+        let Some(breaking) = (0..seatings.len()).min_by_key(|&index| seatings[index].players.len())
+        else {
+            return Ok(());
+        };
+
+        while let Some(player_position) = seatings[breaking]
+            .players
+            .iter()
+            .position(|(_, _, is_movable)| *is_movable)
+        {
+            let destination = (0..seatings.len())
+                .filter(|&index| index != breaking)
+                .filter(|&index| seatings[index].players.len() < MAX_SEATS as usize)
+                .min_by_key(|&index| seatings[index].players.len());
+            let Some(destination) = destination else {
+                break;
+            };
+
+            move_player(
+                seatings,
+                breaking,
+                destination,
+                player_position,
+                messages,
+                storage,
+            )?;
+        }
+
+        return Ok(());
+    }
+
+    loop {
+        let Some(largest) = (0..seatings.len()).max_by_key(|&index| seatings[index].players.len())
+        else {
+            return Ok(());
+        };
+        let Some(smallest) = (0..seatings.len()).min_by_key(|&index| seatings[index].players.len())
+        else {
+            return Ok(());
+        };
+        if seatings[largest].players.len() <= seatings[smallest].players.len() + 1 {
+            return Ok(());
+        }
+
+        let Some(player_position) = seatings[largest]
+            .players
+            .iter()
+            .position(|(_, _, is_movable)| *is_movable)
+        else {
+            return Ok(());
+        };
+
+        move_player(
+            seatings,
+            largest,
+            smallest,
+            player_position,
+            messages,
+            storage,
+        )?;
+    }
+}
+
+fn try_update_tables(mut deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let Some(started_at) = STARTED_AT.may_load(deps.storage)? else {
+        return Err(TableError::GameNotStarted.into());
+    };
+
+    if WINNER.may_load(deps.storage)?.is_some() {
+        return Err(TableError::TournamentFinished.into());
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let mut seatings = load_seatings(&mut deps)?;
+
+    let remaining_players: Vec<&Player> = seatings
+        .iter()
+        .flat_map(|seating| seating.players.iter().map(|(player, _, _)| player))
+        .collect();
+    if let [winner] = remaining_players[..] {
+        let winner = winner.clone();
+        finish_tournament(&winner, &config.tournament, deps.storage)?;
+        return Ok(Response::default());
+    }
+
+    let mut messages = Vec::new();
+    balance_tables(&mut seatings, &mut messages, deps.storage)?;
+
+    for seating in &mut seatings {
+        if seating.players.is_empty() {
+            seating.table.is_closed = true;
+            TABLES.set_at(deps.storage, seating.index, &seating.table)?;
+        }
+    }
+
+    // Every table moves up to the next blind level together.
+    let BlindLevelDuration::Seconds(level_duration) = config.tournament.level_duration else {
+        return Err(ContractError::TimeBasedBlindLevelsOnly);
+    };
+    let levels_passed = env.block.time.seconds().saturating_sub(started_at) / level_duration;
+    let last_level = config.tournament.blind_levels.len() as u64 - 1;
+    let blind_level = levels_passed.min(last_level) as u32;
+
+    if blind_level != BLIND_LEVEL.load(deps.storage)? {
+        BLIND_LEVEL.save(deps.storage, &blind_level)?;
+
+        let big_blind = config.tournament.blind_levels[blind_level as usize];
+        for seating in seatings.iter().filter(|seating| !seating.table.is_closed) {
+            messages.push(table_message(
+                &seating.table,
+                &chain_poker::msg::ExecuteMsg::SetBigBlind { big_blind },
+            )?);
+        }
+    }
+
+    // Tables wait between hands for the players to be moved, and only deal again once there are
+    // enough players seated to play.
+    for seating in &seatings {
+        if !seating.is_started && seating.players.len() >= 2 {
+            messages.push(table_message(
+                &seating.table,
+                &chain_poker::msg::ExecuteMsg::StartGame {},
+            )?);
+        }
+    }
+
+    Ok(Response::new().add_messages(messages))
+}
+
+fn try_claim_prize(deps: DepsMut, sender: Addr) -> Result<Response, ContractError> {
+    if WINNER.may_load(deps.storage)?.is_none() {
+        return Err(TableError::TournamentInProgress.into());
+    }
+
+    let Some(prize) = PRIZES.get(deps.storage, &sender) else {
+        return Err(TableError::NoPrize.into());
+    };
+    PRIZES.remove(deps.storage, &sender)?;
+
+    Ok(Response::new().add_message(send_uscrt(sender, prize)))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::ViewTournament {} => query_tournament(deps),
+    }
+}
+
+fn query_tournament(deps: Deps) -> Result<Binary, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let players: Vec<Player> = PLAYERS.iter(deps.storage)?.collect::<StdResult<_>>()?;
+    let username = |address: &Addr| {
+        players
+            .iter()
+            .find(|player| player.address == *address)
+            .map(|player| player.username.clone())
+            .unwrap_or_default()
+    };
+
+    let tables = TABLES
+        .iter(deps.storage)?
+        .enumerate()
+        .filter_map(|(index, table)| {
+            let table = table.ok().filter(|table| !table.is_closed)?;
+            let players = players
+                .iter()
+                .filter(|player| SEATS.get(deps.storage, &player.address) == Some(index as u32))
+                .map(|player| player.username.clone())
+                .collect();
+
+            Some(TableSeating {
+                address: table.address,
+                code_hash: table.code_hash,
+                players,
+            })
+        })
+        .collect();
+
+    let eliminated = ELIMINATED
+        .iter(deps.storage)?
+        .flatten()
+        .map(|address| username(&address))
+        .collect();
+
+    // Prizes stay listed after they have been claimed.
+    let prizes = finishing_order(&WINNER, &ELIMINATED, deps.storage)?
+        .iter()
+        .zip(share_prize_pool(
+            PRIZE_POOL.load(deps.storage)?,
+            &config.tournament.payouts,
+        ))
+        .map(|(address, prize)| (username(address), prize))
+        .collect();
+
+    let blind_level = BLIND_LEVEL.load(deps.storage)?;
+    let tournament_state = TournamentState {
+        is_started: STARTED_AT.may_load(deps.storage)?.is_some(),
+        registered: players
+            .iter()
+            .map(|player| player.username.clone())
+            .collect(),
+        prize_pool: PRIZE_POOL.load(deps.storage)?,
+        blind_level,
+        big_blind: config.tournament.blind_levels[blind_level as usize],
+        tables,
+        eliminated,
+        prizes,
+        tournament: config.tournament,
+    };
+
+    Ok(to_binary(&tournament_state)?)
+}
@@ -0,0 +1,26 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// Every way a message to the tournament director can fail, displayed in the same
+/// `code: description` format as the table contract's errors. Errors shared with single table
+/// tournaments, such as paying the wrong buy in, use the table contract's codes.
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("std_error: {0}")]
+    Std(#[from] StdError),
+    #[error(transparent)]
+    Tournament(#[from] chain_poker::error::ContractError),
+    #[error("unauthorized: Only the admin of the tournament can start it")]
+    Unauthorized,
+    #[error("unexpected_reply: Unexpected reply with ID {id}")]
+    UnexpectedReply { id: u64 },
+    #[error("table_instantiation_failed: The table could not be created: {reason}")]
+    TableInstantiationFailed { reason: String },
+    #[error(
+        "time_based_blind_levels_only: Blind levels shared between tables must last a number of \
+        seconds"
+    )]
+    TimeBasedBlindLevelsOnly,
+    #[error("not_registered: You are not registered for this tournament")]
+    NotRegistered,
+}
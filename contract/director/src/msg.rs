@@ -0,0 +1,39 @@
+use chain_poker::msg::TournamentConfig;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Code ID of the uploaded `chain-poker` contract the tournament's tables are instantiated
+    /// from.
+    pub table_code_id: u64,
+    pub table_code_hash: String,
+    /// The buy in, starting stack, blind schedule and payout table shared by every table. Blind
+    /// levels must last a number of seconds, as each table deals hands at its own pace.
+    pub tournament: TournamentConfig,
+    pub turn_time_limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Enter the tournament, paying exactly the buy in.
+    Register { username: String },
+    /// Leave the tournament before it starts, taking back the buy in.
+    Unregister {},
+    /// Seat every registered player across as few tables as possible, and start dealing. Only the
+    /// admin may do this.
+    StartTournament {},
+    /// Eliminate busted players, raise the blinds on schedule, move players to balance the tables
+    /// or break one up, and deal the next hand at every table waiting between hands. Tables do
+    /// not deal again until this is called, so anyone may call it after every hand.
+    UpdateTables {},
+    /// Collect the prize won by finishing in the money, once the tournament is over.
+    ClaimPrize {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    ViewTournament {},
+}
@@ -0,0 +1,88 @@
+use chain_poker::msg::TournamentConfig;
+use cosmwasm_std::Addr;
+use secret_toolkit::{
+    serialization::Bincode2,
+    storage::{AppendStore, Item, Keymap, KeymapBuilder, WithoutIter},
+};
+use serde::{Deserialize, Serialize};
+
+pub static CONFIG: Item<Config> = Item::new(b"config");
+/// Every player registered for the tournament, in order of registration.
+pub static PLAYERS: AppendStore<Player> = AppendStore::new(b"players");
+/// uSCRT paid in by every registered player.
+pub static PRIZE_POOL: Item<u128> = Item::new(b"prize_pool");
+/// Block time, in seconds, at which the tournament started.
+pub static STARTED_AT: Item<u64> = Item::new(b"started_at");
+/// The current blind level, as an index into the tournament's `blind_levels`.
+pub static BLIND_LEVEL: Item<u32> = Item::new(b"blind_level");
+/// Every table of the tournament. A table's index is the ID of the reply to its instantiation.
+pub static TABLES: AppendStore<TableInfo> = AppendStore::new(b"tables");
+/// The index of the table each player still in the tournament is seated at.
+pub static SEATS: Keymap<Addr, u32, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"seats").without_iter().build();
+/// The players to seat at each table once it has been instantiated, keyed by the reply ID.
+pub static PENDING_SEATINGS: Keymap<u64, Vec<Player>, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"pending_seatings")
+        .without_iter()
+        .build();
+/// The chips each player still in the tournament had when their table last waited between
+/// hands, which are the chips they started their current or next hand with.
+pub static STARTING_STACKS: Keymap<Addr, u128, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"starting_stacks")
+        .without_iter()
+        .build();
+/// Players knocked out of the tournament, in the order they were eliminated.
+pub static ELIMINATED: AppendStore<Addr> = AppendStore::new(b"eliminated");
+/// The last player left with chips, once the tournament is over.
+pub static WINNER: Item<Addr> = Item::new(b"winner");
+/// uSCRT won by each player who finished in the money, until they claim it.
+pub static PRIZES: Keymap<Addr, u128, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"prizes").without_iter().build();
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Config {
+    pub admin: Addr,
+    pub table_code_id: u64,
+    pub table_code_hash: String,
+    pub tournament: TournamentConfig,
+    pub turn_time_limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Player {
+    pub address: Addr,
+    pub username: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TableInfo {
+    pub address: Addr,
+    pub code_hash: String,
+    /// Whether every player has been moved away from the table, so it is no longer used.
+    pub is_closed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TableSeating {
+    pub address: Addr,
+    pub code_hash: String,
+    /// Usernames of the players still in the tournament who are seated at the table.
+    pub players: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TournamentState {
+    pub tournament: TournamentConfig,
+    pub is_started: bool,
+    /// Usernames of every registered player.
+    pub registered: Vec<String>,
+    pub prize_pool: u128,
+    pub blind_level: u32,
+    pub big_blind: u32,
+    /// The tables still in use.
+    pub tables: Vec<TableSeating>,
+    /// Usernames of the players knocked out, in the order they were eliminated.
+    pub eliminated: Vec<String>,
+    /// The prize won by each player who finished in the money, in order of finishing position.
+    pub prizes: Vec<(String, u128)>,
+}
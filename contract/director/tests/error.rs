@@ -0,0 +1,13 @@
+use chain_poker_director::error::ContractError;
+
+#[test]
+fn tournament_errors_keep_the_table_contract_code() {
+    assert_eq!(
+        ContractError::from(chain_poker::error::ContractError::AlreadyRegistered).to_string(),
+        "already_registered: You are already registered for this tournament"
+    );
+    assert_eq!(
+        ContractError::NotRegistered.to_string(),
+        "not_registered: You are not registered for this tournament"
+    );
+}
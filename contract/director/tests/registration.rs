@@ -0,0 +1,149 @@
+use chain_poker::{
+    error::ContractError as TableError,
+    msg::{BlindLevelDuration, TournamentConfig},
+};
+use chain_poker_director::{
+    contract::{execute, instantiate, query},
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::TournamentState,
+};
+use cosmwasm_std::{
+    coins, from_binary,
+    testing::{mock_dependencies, mock_env, mock_info},
+    BankMsg, CosmosMsg,
+};
+
+const BUY_IN: u128 = 1_000_000;
+
+fn tournament() -> TournamentConfig {
+    TournamentConfig {
+        buy_in: BUY_IN.into(),
+        starting_stack: 1500,
+        blind_levels: vec![20, 40, 60, 100],
+        level_duration: BlindLevelDuration::Seconds(600),
+        payouts: vec![65, 35],
+    }
+}
+
+#[test]
+fn players_register_and_unregister_before_the_start() {
+    let mut deps = mock_dependencies();
+
+    let hand_based_levels = InstantiateMsg {
+        table_code_id: 1,
+        table_code_hash: "table_code_hash".to_string(),
+        tournament: TournamentConfig {
+            level_duration: BlindLevelDuration::Hands(10),
+            ..tournament()
+        },
+        turn_time_limit: None,
+    };
+    let error = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("director_admin", &[]),
+        hand_based_levels.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::TimeBasedBlindLevelsOnly);
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("director_admin", &[]),
+        InstantiateMsg {
+            tournament: tournament(),
+            ..hand_based_levels
+        },
+    )
+    .unwrap();
+
+    let register = |username: &str| ExecuteMsg::Register {
+        username: username.to_string(),
+    };
+
+    let error = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(BUY_IN / 2, "uscrt")),
+        register("alice"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::Tournament(TableError::TournamentBuyIn { buy_in: BUY_IN })
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &coins(BUY_IN, "uscrt")),
+        register("alice"),
+    )
+    .unwrap();
+
+    let error = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(BUY_IN, "uscrt")),
+        register("ALICE"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::Tournament(TableError::UsernameTaken {
+            username: "ALICE".to_string()
+        })
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &coins(BUY_IN, "uscrt")),
+        register("bob"),
+    )
+    .unwrap();
+
+    let error = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("bob", &[]),
+        ExecuteMsg::StartTournament {},
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::Unauthorized);
+
+    let response = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("alice", &[]),
+        ExecuteMsg::Unregister {},
+    )
+    .unwrap();
+    assert_eq!(
+        response.messages[0].msg,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: "alice".to_string(),
+            amount: coins(BUY_IN, "uscrt"),
+        })
+    );
+
+    let tournament_state: TournamentState =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ViewTournament {}).unwrap())
+            .unwrap();
+    assert_eq!(tournament_state.registered, vec!["bob".to_string()]);
+    assert_eq!(tournament_state.prize_pool, BUY_IN);
+
+    let error = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("director_admin", &[]),
+        ExecuteMsg::StartTournament {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        ContractError::Tournament(TableError::NotEnoughPlayers)
+    );
+}
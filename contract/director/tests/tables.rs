@@ -0,0 +1,300 @@
+use std::sync::MutexGuard;
+
+use chain_poker::{
+    msg::{BettingStructure, BlindLevelDuration, GameVariant, TournamentConfig},
+    state::{LobbyConfig, PreStartState},
+    test_support::isolated_deps,
+};
+use chain_poker_director::{
+    contract::{execute, instantiate, query, reply},
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    state::{TournamentState, ELIMINATED, PLAYERS, TABLES},
+};
+use chain_poker_factory::contract::encode_instantiate_response;
+use cosmwasm_std::{
+    coins, from_binary,
+    testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+    to_binary, Binary, ContractResult, CosmosMsg, OwnedDeps, Reply, SubMsgResponse, SubMsgResult,
+    SystemResult, WasmMsg, WasmQuery,
+};
+
+const BUY_IN: u128 = 1_000_000;
+const TABLE_ADDRESSES: [&str; 2] = ["table_zero", "table_one"];
+
+fn setup() -> (
+    OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    MutexGuard<'static, ()>,
+) {
+    isolated_deps(|storage| {
+        PLAYERS.clear(storage);
+        TABLES.clear(storage);
+        ELIMINATED.clear(storage);
+    })
+}
+
+/// The table messages sent by the director, as (table address, message) pairs.
+fn table_messages(messages: &[CosmosMsg]) -> Vec<(String, chain_poker::msg::ExecuteMsg)> {
+    messages
+        .iter()
+        .map(|message| {
+            let CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) = message
+            else {
+                panic!("expected a message to a table, got {message:?}");
+            };
+            (contract_addr.clone(), from_binary(msg).unwrap())
+        })
+        .collect()
+}
+
+/// Register twelve players and start the tournament, returning the messages sent to each of the
+/// two tables once it has been instantiated.
+fn start_tournament(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+) -> Vec<Vec<(String, chain_poker::msg::ExecuteMsg)>> {
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("director_admin", &[]),
+        InstantiateMsg {
+            table_code_id: 1,
+            table_code_hash: "table_code_hash".to_string(),
+            tournament: TournamentConfig {
+                buy_in: BUY_IN.into(),
+                starting_stack: 1500,
+                blind_levels: vec![20, 40, 60, 100],
+                level_duration: BlindLevelDuration::Seconds(600),
+                payouts: vec![65, 35],
+            },
+            turn_time_limit: None,
+        },
+    )
+    .unwrap();
+
+    for player in 0..12 {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&format!("player{player}"), &coins(BUY_IN, "uscrt")),
+            ExecuteMsg::Register {
+                username: format!("player{player}"),
+            },
+        )
+        .unwrap();
+    }
+
+    let response = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("director_admin", &[]),
+        ExecuteMsg::StartTournament {},
+    )
+    .unwrap();
+    assert_eq!(response.messages.len(), 2);
+
+    response
+        .messages
+        .iter()
+        .zip(TABLE_ADDRESSES)
+        .map(|(submessage, address)| {
+            let response = reply(
+                deps.as_mut(),
+                mock_env(),
+                Reply {
+                    id: submessage.id,
+                    result: SubMsgResult::Ok(SubMsgResponse {
+                        events: vec![],
+                        data: Some(encode_instantiate_response(address)),
+                    }),
+                },
+            )
+            .unwrap();
+
+            let messages: Vec<CosmosMsg> = response.messages.into_iter().map(|m| m.msg).collect();
+            table_messages(&messages)
+        })
+        .collect()
+}
+
+/// The state of a tournament table with the given stacks.
+fn table_state(
+    balances: Vec<(String, u128)>,
+    players_in_hand: Vec<String>,
+    is_started: bool,
+) -> SystemResult<ContractResult<Binary>> {
+    SystemResult::Ok(ContractResult::Ok(
+        to_binary(&PreStartState {
+            admin: "director".to_string(),
+            lobby_config: LobbyConfig {
+                big_blind: 20,
+                max_buy_in_bb: 0,
+                min_buy_in_bb: 0,
+                turn_time_limit: None,
+                tournament: None,
+                ante: None,
+                allow_straddle: false,
+                betting_structure: BettingStructure::NoLimit,
+                variant: GameVariant::Holdem,
+                disable_spectators: false,
+            },
+            is_started,
+            balances,
+            occupied_seats: 0,
+            players_in_hand,
+            tournament: None,
+        })
+        .unwrap(),
+    ))
+}
+
+/// Mock every table with the players seated at it, as players alternate between the tables.
+/// Each player's stack is given by `stack`.
+fn mock_tables(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>, stack: fn(u32) -> u128) {
+    deps.querier.update_wasm(move |query| {
+        let WasmQuery::Smart { contract_addr, .. } = query else {
+            panic!("unexpected query {query:?}");
+        };
+        let table = TABLE_ADDRESSES
+            .iter()
+            .position(|&address| address == contract_addr.as_str())
+            .unwrap() as u32;
+        let balances = (table..12)
+            .step_by(2)
+            .map(|player| (format!("player{player}"), stack(player)))
+            .collect();
+
+        table_state(balances, vec![], false)
+    });
+}
+
+fn update_tables(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) -> Vec<CosmosMsg> {
+    let response = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone", &[]),
+        ExecuteMsg::UpdateTables {},
+    )
+    .unwrap();
+    response.messages.into_iter().map(|m| m.msg).collect()
+}
+
+fn tournament_state(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> TournamentState {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ViewTournament {}).unwrap()).unwrap()
+}
+
+#[test]
+fn tables_are_seated_balanced_and_broken_up() {
+    let (mut deps, _lock) = setup();
+
+    for (table_messages, address) in start_tournament(&mut deps).into_iter().zip(TABLE_ADDRESSES) {
+        assert_eq!(table_messages.len(), 7);
+        assert!(table_messages
+            .iter()
+            .all(|(table_address, _)| table_address == address));
+        assert_eq!(
+            table_messages[6].1,
+            chain_poker::msg::ExecuteMsg::StartGame {}
+        );
+    }
+
+    // Five of the six players at the first table have busted, and one at the second table is
+    // all in and waiting on the end of the hand.
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { contract_addr, .. } = query else {
+            panic!("unexpected query {query:?}");
+        };
+        let table = TABLE_ADDRESSES
+            .iter()
+            .position(|address| address == contract_addr)
+            .unwrap();
+        let balances = (table..12)
+            .step_by(2)
+            .map(|player| {
+                let balance = if player == 0 || table == 1 && player != 1 {
+                    1500
+                } else {
+                    0
+                };
+                (format!("player{player}"), balance)
+            })
+            .collect();
+        let players_in_hand = if table == 1 {
+            vec!["player1".to_string()]
+        } else {
+            vec![]
+        };
+
+        table_state(balances, players_in_hand, true)
+    });
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(1200);
+    let response = execute(
+        deps.as_mut(),
+        env,
+        mock_info("anyone", &[]),
+        ExecuteMsg::UpdateTables {},
+    )
+    .unwrap();
+    let messages: Vec<CosmosMsg> = response.messages.into_iter().map(|m| m.msg).collect();
+
+    // The seven players left fit around one table, so the first table is broken up.
+    assert_eq!(
+        table_messages(&messages),
+        vec![
+            (
+                "table_zero".to_string(),
+                chain_poker::msg::ExecuteMsg::UnseatPlayer {
+                    address: "player0".to_string(),
+                },
+            ),
+            (
+                "table_one".to_string(),
+                chain_poker::msg::ExecuteMsg::SeatPlayer {
+                    address: "player0".to_string(),
+                    username: "player0".to_string(),
+                    stack: 1500u128.into(),
+                },
+            ),
+            (
+                "table_one".to_string(),
+                chain_poker::msg::ExecuteMsg::SetBigBlind { big_blind: 60 },
+            ),
+        ]
+    );
+
+    let tournament_state = tournament_state(&deps);
+    assert_eq!(tournament_state.blind_level, 2);
+    assert_eq!(tournament_state.eliminated.len(), 5);
+    assert_eq!(tournament_state.tables.len(), 1);
+    assert_eq!(tournament_state.tables[0].players.len(), 7);
+}
+
+#[test]
+fn players_busting_at_different_tables_are_eliminated_by_starting_stack() {
+    let (mut deps, _lock) = setup();
+    start_tournament(&mut deps);
+
+    // Between hands, player0 and player2 at the first table have more chips than player1 at the
+    // second table.
+    mock_tables(&mut deps, |player| match player {
+        0 => 3000,
+        1 => 500,
+        2 => 1000,
+        _ => 1500,
+    });
+    update_tables(&mut deps);
+    assert!(tournament_state(&deps).eliminated.is_empty());
+
+    // All three bust in their next hands.
+    mock_tables(&mut deps, |player| match player {
+        0..=2 => 0,
+        _ => 1500,
+    });
+    update_tables(&mut deps);
+    assert_eq!(
+        tournament_state(&deps).eliminated,
+        vec!["player1", "player2", "player0"]
+    );
+}
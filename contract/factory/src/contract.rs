@@ -0,0 +1,268 @@
+use chain_poker::state::{PreStartState, MAX_SEATS};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, SubMsg,
+    WasmMsg,
+};
+
+use crate::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TableFilters},
+    state::{Config, ListTablesResponse, TableInfo, TableSummary, CONFIG, PENDING_CREATOR, TABLES},
+};
+
+const CREATE_TABLE_REPLY_ID: u64 = 1;
+/// Number of tables on each page when no page size is given.
+const DEFAULT_PAGE_SIZE: u32 = 20;
+/// Most tables on each page, as each table listed is asked for its state.
+const MAX_PAGE_SIZE: u32 = 50;
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = Config {
+        admin: info.sender,
+        table_code_id: msg.table_code_id,
+        table_code_hash: msg.table_code_hash,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::CreateTable { table } => try_create_table(deps, env, info.sender, table),
+        ExecuteMsg::SetTableCode { code_id, code_hash } => {
+            try_set_table_code(deps, info.sender, code_id, code_hash)
+        }
+    }
+}
+
+fn try_create_table(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    table: chain_poker::msg::InstantiateMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let table_id = TABLES.get_len(deps.storage)?;
+
+    // The player who asked for the table controls it, rather than the factory.
+    let table = chain_poker::msg::InstantiateMsg {
+        admin: Some(sender.to_string()),
+        ..table
+    };
+    PENDING_CREATOR.save(deps.storage, &sender)?;
+
+    let instantiate_message = WasmMsg::Instantiate {
+        admin: None,
+        code_id: config.table_code_id,
+        code_hash: config.table_code_hash,
+        msg: to_binary(&table)?,
+        funds: vec![],
+        label: format!("chain-poker-table-{}-{table_id}", env.contract.address),
+    };
+
+    Ok(Response::new().add_submessage(SubMsg::reply_on_success(
+        instantiate_message,
+        CREATE_TABLE_REPLY_ID,
+    )))
+}
+
+fn try_set_table_code(
+    deps: DepsMut,
+    sender: Addr,
+    code_id: u64,
+    code_hash: String,
+) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if sender != config.admin {
+        return Err(ContractError::Unauthorized);
+    }
+
+    config.table_code_id = code_id;
+    config.table_code_hash = code_hash;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+/// Read the address of a newly instantiated contract from the protobuf encoded
+/// `MsgInstantiateContractResponse` in the reply data. The address is field 1, a length delimited
+/// string.
+pub fn parse_instantiate_address(data: &[u8]) -> Option<String> {
+    let (&tag, rest) = data.split_first()?;
+    if tag != 0x0a {
+        return None;
+    }
+
+    // The length is a varint, seven bits at a time with the high bit set on all but the last byte.
+    let mut length = 0usize;
+    let mut length_bytes = 0;
+    for (index, byte) in rest.iter().enumerate().take(4) {
+        length |= ((byte & 0x7f) as usize) << (7 * index);
+        if byte & 0x80 == 0 {
+            length_bytes = index + 1;
+            break;
+        }
+    }
+
+    let address = rest
+        .get(length_bytes..length_bytes + length)
+        .filter(|_| length_bytes > 0)?;
+    String::from_utf8(address.to_vec()).ok()
+}
+
+/// Encode a `MsgInstantiateContractResponse` holding only the contract's address, as the chain
+/// does in the reply data. This is the inverse of `parse_instantiate_address`.
+pub fn encode_instantiate_response(address: &str) -> Binary {
+    let mut data = vec![0x0a];
+
+    let mut length = address.len();
+    while length >= 0x80 {
+        data.push((length & 0x7f) as u8 | 0x80);
+        length >>= 7;
+    }
+    data.push(length as u8);
+
+    data.extend(address.as_bytes());
+    Binary(data)
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != CREATE_TABLE_REPLY_ID {
+        return Err(ContractError::UnexpectedReply { id: msg.id });
+    }
+
+    let response = msg
+        .result
+        .into_result()
+        .map_err(|reason| ContractError::TableInstantiationFailed { reason })?;
+    let data = response
+        .data
+        .ok_or_else(|| ContractError::TableInstantiationFailed {
+            reason: "The reply did not contain any data".to_string(),
+        })?;
+    let address = parse_instantiate_address(data.as_slice()).ok_or_else(|| {
+        ContractError::TableInstantiationFailed {
+            reason: "The reply did not contain the table's address".to_string(),
+        }
+    })?;
+    let address = deps.api.addr_validate(&address)?;
+
+    // The table's settings are read back from the table, as it is the source of truth for them.
+    let config = CONFIG.load(deps.storage)?;
+    let pre_start_state: PreStartState = deps.querier.query_wasm_smart(
+        &config.table_code_hash,
+        address.as_str(),
+        &chain_poker::msg::QueryMsg::ViewPreStartState {},
+    )?;
+
+    let table = TableInfo {
+        address: address.clone(),
+        code_hash: config.table_code_hash,
+        creator: PENDING_CREATOR.load(deps.storage)?,
+        lobby_config: pre_start_state.lobby_config,
+    };
+    TABLES.push(deps.storage, &table)?;
+    PENDING_CREATOR.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("table_address", address))
+}
+
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::ListTables {
+            filters,
+            page,
+            page_size,
+        } => query_list_tables(deps, filters.unwrap_or_default(), page, page_size),
+    }
+}
+
+/// Look up the current state of a table, which is only known to the table itself.
+fn summarise_table(deps: Deps, id: u32, table: TableInfo) -> StdResult<TableSummary> {
+    let pre_start_state: PreStartState = deps.querier.query_wasm_smart(
+        &table.code_hash,
+        table.address.as_str(),
+        &chain_poker::msg::QueryMsg::ViewPreStartState {},
+    )?;
+
+    Ok(TableSummary {
+        id,
+        address: table.address,
+        code_hash: table.code_hash,
+        admin: pre_start_state.admin,
+        lobby_config: pre_start_state.lobby_config,
+        is_started: pre_start_state.is_started,
+        occupied_seats: pre_start_state.occupied_seats,
+        max_seats: MAX_SEATS as u8,
+    })
+}
+
+fn query_list_tables(
+    deps: Deps,
+    filters: TableFilters,
+    page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    // Only the tables on the requested page are asked for their state, so the work done doesn't
+    // grow with the number of tables.
+    let page = page.unwrap_or(0);
+    let page_size = page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+    // Pages starting past the largest table id are empty, rather than overflowing.
+    let first_id = page.saturating_mul(page_size);
+    let page_tables = TABLES
+        .iter(deps.storage)?
+        .skip(first_id as usize)
+        .take(page_size as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut tables = Vec::new();
+
+    for (table, id) in page_tables.into_iter().zip(first_id..) {
+        // Stakes never change, so they are filtered before asking the table for its state.
+        let big_blind = table.lobby_config.big_blind;
+        if filters
+            .min_big_blind
+            .is_some_and(|minimum| big_blind < minimum)
+            || filters
+                .max_big_blind
+                .is_some_and(|maximum| big_blind > maximum)
+        {
+            continue;
+        }
+
+        let summary = summarise_table(deps, id, table)?;
+        let has_open_seats = summary.occupied_seats < summary.max_seats;
+        if filters
+            .has_open_seats
+            .is_some_and(|filter| filter != has_open_seats)
+            || filters
+                .is_started
+                .is_some_and(|filter| filter != summary.is_started)
+        {
+            continue;
+        }
+
+        tables.push(summary);
+    }
+
+    let total_tables = TABLES.get_len(deps.storage)?;
+
+    Ok(to_binary(&ListTablesResponse {
+        tables,
+        total_tables,
+    })?)
+}
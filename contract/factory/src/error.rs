@@ -0,0 +1,16 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+/// Every way a message to the factory can fail, displayed in the same `code: description` format
+/// as the table contract's errors.
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("std_error: {0}")]
+    Std(#[from] StdError),
+    #[error("unauthorized: Only the admin of the factory can change the table code")]
+    Unauthorized,
+    #[error("unexpected_reply: Unexpected reply with ID {id}")]
+    UnexpectedReply { id: u64 },
+    #[error("table_instantiation_failed: The table could not be created: {reason}")]
+    TableInstantiationFailed { reason: String },
+}
@@ -0,0 +1,42 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    pub table_code_id: u64,
+    pub table_code_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Instantiate a new table, with the sender as its admin.
+    CreateTable {
+        table: chain_poker::msg::InstantiateMsg,
+    },
+    /// Instantiate new tables from a different upload of the table contract. Only the admin of
+    /// the factory may do this.
+    SetTableCode { code_id: u64, code_hash: String },
+}
+
+/// Tables must match every filter that is given.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, JsonSchema)]
+pub struct TableFilters {
+    pub min_big_blind: Option<u32>,
+    pub max_big_blind: Option<u32>,
+    pub has_open_seats: Option<bool>,
+    pub is_started: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// View the tables matching the filters from a page of every table, starting from page 0. A
+    /// page may hold fewer than `page_size` tables, as the filters are applied after paging.
+    /// Pages hold at most 50 tables.
+    ListTables {
+        filters: Option<TableFilters>,
+        page: Option<u32>,
+        page_size: Option<u32>,
+    },
+}
@@ -0,0 +1,47 @@
+use chain_poker::state::LobbyConfig;
+use cosmwasm_std::Addr;
+use secret_toolkit::storage::{AppendStore, Item};
+use serde::{Deserialize, Serialize};
+
+pub static CONFIG: Item<Config> = Item::new(b"config");
+/// Every table created by the factory, in order of creation. A table's ID is its index.
+pub static TABLES: AppendStore<TableInfo> = AppendStore::new(b"tables");
+/// The player who asked for the table currently being instantiated, until its address arrives in
+/// the reply.
+pub static PENDING_CREATOR: Item<Addr> = Item::new(b"pending_creator");
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Config {
+    pub admin: Addr,
+    /// Code ID of the uploaded `chain-poker` contract new tables are instantiated from.
+    pub table_code_id: u64,
+    pub table_code_hash: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TableInfo {
+    pub address: Addr,
+    pub code_hash: String,
+    pub creator: Addr,
+    pub lobby_config: LobbyConfig,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TableSummary {
+    pub id: u32,
+    pub address: Addr,
+    pub code_hash: String,
+    /// Username of the table's admin.
+    pub admin: String,
+    pub lobby_config: LobbyConfig,
+    pub is_started: bool,
+    pub occupied_seats: u8,
+    pub max_seats: u8,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ListTablesResponse {
+    pub tables: Vec<TableSummary>,
+    /// Number of tables created by the factory, across every page.
+    pub total_tables: u32,
+}
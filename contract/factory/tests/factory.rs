@@ -0,0 +1,238 @@
+use chain_poker::{
+    msg::{BettingStructure, GameVariant},
+    state::{LobbyConfig, PreStartState},
+};
+use chain_poker_factory::{
+    contract::{encode_instantiate_response, execute, instantiate, query, reply},
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg, TableFilters},
+    state::ListTablesResponse,
+};
+use cosmwasm_std::{
+    from_binary,
+    testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+    to_binary, ContractResult, CosmosMsg, OwnedDeps, Reply, SubMsgResponse, SubMsgResult,
+    SystemResult, WasmMsg, WasmQuery,
+};
+
+const TABLE_CODE_HASH: &str = "table_code_hash";
+
+/// Each mock table's address, big blind, whether it has started and its number of occupied seats.
+const TABLES: [(&str, u32, bool, u8); 3] = [
+    ("table_one", 100, false, 2),
+    ("table_two", 500, false, 9),
+    ("table_three", 1000, true, 4),
+];
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    deps.querier.update_wasm(|query| {
+        let WasmQuery::Smart { contract_addr, .. } = query else {
+            panic!("unexpected query {query:?}");
+        };
+        let (_, big_blind, is_started, occupied_seats) = TABLES
+            .into_iter()
+            .find(|(address, ..)| address == contract_addr)
+            .unwrap();
+
+        SystemResult::Ok(ContractResult::Ok(
+            to_binary(&PreStartState {
+                admin: "creator".to_string(),
+                lobby_config: LobbyConfig {
+                    big_blind,
+                    max_buy_in_bb: 100,
+                    min_buy_in_bb: 50,
+                    turn_time_limit: None,
+                    tournament: None,
+                    ante: None,
+                    allow_straddle: false,
+                    betting_structure: BettingStructure::NoLimit,
+                    variant: GameVariant::Holdem,
+                    disable_spectators: false,
+                },
+                is_started,
+                balances: vec![],
+                occupied_seats,
+                players_in_hand: vec![],
+                tournament: None,
+            })
+            .unwrap(),
+        ))
+    });
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("factory_admin", &[]),
+        InstantiateMsg {
+            table_code_id: 1,
+            table_code_hash: TABLE_CODE_HASH.to_string(),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn list_tables(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    filters: Option<TableFilters>,
+) -> ListTablesResponse {
+    list_page(deps, filters, None, None)
+}
+
+fn list_page(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    filters: Option<TableFilters>,
+    page: Option<u32>,
+    page_size: Option<u32>,
+) -> ListTablesResponse {
+    let response = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::ListTables {
+            filters,
+            page,
+            page_size,
+        },
+    )
+    .unwrap();
+    from_binary(&response).unwrap()
+}
+
+#[test]
+fn created_tables_are_listed() {
+    let mut deps = setup();
+
+    for (address, big_blind, ..) in TABLES {
+        let response = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            ExecuteMsg::CreateTable {
+                table: chain_poker::msg::InstantiateMsg {
+                    username: "creator".to_string(),
+                    big_blind,
+                    max_buy_in_bb: 100,
+                    min_buy_in_bb: 50,
+                    turn_time_limit: None,
+                    admin: None,
+                    tournament: None,
+                    director: None,
+                    ante: None,
+                    allow_straddle: false,
+                    betting_structure: BettingStructure::NoLimit,
+                    variant: GameVariant::Holdem,
+                    disable_spectators: false,
+                },
+            },
+        )
+        .unwrap();
+
+        let CosmosMsg::Wasm(WasmMsg::Instantiate { msg, code_hash, .. }) =
+            &response.messages[0].msg
+        else {
+            panic!("expected the table to be instantiated");
+        };
+        let table: chain_poker::msg::InstantiateMsg = from_binary(msg).unwrap();
+        assert_eq!(table.admin, Some("creator".to_string()));
+        assert_eq!(code_hash, TABLE_CODE_HASH);
+
+        reply(
+            deps.as_mut(),
+            mock_env(),
+            Reply {
+                id: response.messages[0].id,
+                result: SubMsgResult::Ok(SubMsgResponse {
+                    events: vec![],
+                    data: Some(encode_instantiate_response(address)),
+                }),
+            },
+        )
+        .unwrap();
+    }
+
+    let all_tables = list_tables(&deps, None);
+    assert_eq!(all_tables.total_tables, 3);
+    assert_eq!(all_tables.tables[1].address.as_str(), "table_two");
+
+    let open_tables = list_tables(
+        &deps,
+        Some(TableFilters {
+            has_open_seats: Some(true),
+            is_started: Some(false),
+            ..Default::default()
+        }),
+    );
+    assert_eq!(open_tables.tables.len(), 1);
+    assert_eq!(open_tables.tables[0].address.as_str(), "table_one");
+
+    let high_stakes_tables = list_tables(
+        &deps,
+        Some(TableFilters {
+            min_big_blind: Some(500),
+            ..Default::default()
+        }),
+    );
+    assert_eq!(high_stakes_tables.tables.len(), 2);
+    assert_eq!(high_stakes_tables.tables[1].id, 2);
+
+    let second_page = list_page(&deps, None, Some(1), Some(2));
+    assert_eq!(second_page.total_tables, 3);
+    assert_eq!(second_page.tables.len(), 1);
+    assert_eq!(second_page.tables[0].id, 2);
+
+    // Page sizes are capped, and pages past the last table are empty.
+    assert_eq!(
+        list_page(&deps, None, Some(0), Some(u32::MAX)).tables.len(),
+        3
+    );
+    assert!(list_page(&deps, None, Some(u32::MAX), Some(u32::MAX))
+        .tables
+        .is_empty());
+
+    // Filters are applied to each page, so the page holding only table one has no matches.
+    let first_page = list_page(
+        &deps,
+        Some(TableFilters {
+            min_big_blind: Some(500),
+            ..Default::default()
+        }),
+        Some(0),
+        Some(1),
+    );
+    assert_eq!(first_page.total_tables, 3);
+    assert!(first_page.tables.is_empty());
+}
+
+#[test]
+fn only_the_admin_can_set_the_table_code() {
+    let mut deps = setup();
+
+    let error = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("creator", &[]),
+        ExecuteMsg::SetTableCode {
+            code_id: 2,
+            code_hash: "new_code_hash".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error, ContractError::Unauthorized);
+    assert_eq!(
+        error.to_string(),
+        "unauthorized: Only the admin of the factory can change the table code"
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("factory_admin", &[]),
+        ExecuteMsg::SetTableCode {
+            code_id: 2,
+            code_hash: "new_code_hash".to_string(),
+        },
+    )
+    .unwrap();
+}
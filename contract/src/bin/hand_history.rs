@@ -0,0 +1,52 @@
+//! Convert hand history query responses, exported as JSON, into PokerStars style hand history
+//! files for poker tracking software.
+//!
+//! Each input file holds either a single `hand_history` query response, or an array of them. The
+//! converted hands are written next to the input file, with a `.txt` extension.
+//!
+//! ```bash
+//! cargo run --bin hand_history --features hand-history -- hand_1.json hand_2.json
+//! ```
+
+use std::{env, fs, path::Path, process};
+
+use chain_poker::{pokerstars::format_hand_histories, state::HandHistoryResponse};
+use cosmwasm_std::from_slice;
+
+fn convert(input_path: &Path) -> Result<(), String> {
+    let json = fs::read(input_path).map_err(|error| error.to_string())?;
+
+    let pages = match from_slice::<Vec<HandHistoryResponse>>(&json) {
+        Ok(pages) => pages,
+        Err(_) => {
+            vec![from_slice::<HandHistoryResponse>(&json).map_err(|error| error.to_string())?]
+        }
+    };
+
+    let output_path = input_path.with_extension("txt");
+    fs::write(&output_path, format_hand_histories(&pages) + "\n")
+        .map_err(|error| error.to_string())?;
+
+    println!("{} -> {}", input_path.display(), output_path.display());
+    Ok(())
+}
+
+fn main() {
+    let input_paths: Vec<String> = env::args().skip(1).collect();
+    if input_paths.is_empty() {
+        eprintln!("Usage: hand_history <exported query JSON>...");
+        process::exit(1);
+    }
+
+    let mut has_failed = false;
+    for input_path in &input_paths {
+        if let Err(error) = convert(Path::new(input_path)) {
+            eprintln!("{input_path}: {error}");
+            has_failed = true;
+        }
+    }
+
+    if has_failed {
+        process::exit(1);
+    }
+}
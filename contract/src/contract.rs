@@ -1,62 +1,137 @@
 mod execute;
 mod query;
 
-use cosmwasm_std::{
-    entry_point, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
+use execute::{
+    set_username, try_all_in, try_buy_in, try_call, try_check, try_claim_timeout, try_fold,
+    try_raise, try_seat_player, try_set_big_blind, try_set_show_uncontested, try_set_straddle,
+    try_sit_in, try_sit_out, try_start_game, try_unseat_player, try_withdraw_chips,
+};
+use query::{
+    query_game_state, query_hand_history, query_last_showdown, query_pre_start_state,
+    query_spectator_state, query_verify_hand,
 };
-use execute::{try_buy_in, try_place_bet, try_start_game, try_withdraw_chips};
-use query::{query_game_state, query_pre_start_state};
 
 use crate::{
-    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
-    state::{LobbyConfig, ADMIN, IS_STARTED, LOBBY_CONFIG, POT, REVEALED_CARDS, USERNAMES},
+    error::ContractError,
+    msg::{Ante, ExecuteMsg, InstantiateMsg, QueryMsg},
+    poker::tournament::validate_config,
+    state::{
+        LobbyConfig, ADMIN, DIRECTOR, IS_STARTED, LOBBY_CONFIG, POT, PRIZE_POOL, REVEALED_CARDS,
+    },
 };
 
-#[entry_point]
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     if msg.min_buy_in_bb > msg.max_buy_in_bb {
-        return Err(StdError::generic_err(
-            "Min buy in must be less than or equal to the max buy in",
-        ));
+        return Err(ContractError::InvalidBuyInRange);
+    }
+
+    // Tournaments start at the first blind level, which is shown as the lobby's big blind.
+    let big_blind = match msg.tournament {
+        Some(ref tournament) => {
+            validate_config(tournament)?;
+            tournament.blind_levels[0]
+        }
+        None => msg.big_blind,
+    };
+
+    if let Some(Ante::EveryPlayer(ante)) = msg.ante {
+        if ante == 0 || ante > big_blind {
+            return Err(ContractError::InvalidAnte);
+        }
     }
 
     let lobby_config = LobbyConfig {
-        big_blind: msg.big_blind,
+        big_blind,
         max_buy_in_bb: msg.max_buy_in_bb,
         min_buy_in_bb: msg.min_buy_in_bb,
+        turn_time_limit: msg.turn_time_limit,
+        tournament: msg.tournament,
+        ante: msg.ante,
+        allow_straddle: msg.allow_straddle,
+        betting_structure: msg.betting_structure,
+        variant: msg.variant,
+        disable_spectators: msg.disable_spectators,
     };
-    let admin_canonical_address = deps.api.addr_canonicalize(info.sender.as_str())?;
+    if let Some(director) = msg.director {
+        // The director runs the table, but does not play at it.
+        let director = deps.api.addr_validate(&director)?;
+        let director_canonical_address = deps.api.addr_canonicalize(director.as_str())?;
+        ADMIN.save(deps.storage, &director_canonical_address)?;
+        DIRECTOR.save(deps.storage, &director_canonical_address)?;
+    } else {
+        // Lobbies created through a factory contract name the player who asked for the lobby.
+        let admin = match msg.admin {
+            Some(admin) => deps.api.addr_validate(&admin)?,
+            None => info.sender,
+        };
+        let admin_canonical_address = deps.api.addr_canonicalize(admin.as_str())?;
 
-    ADMIN.save(deps.storage, &admin_canonical_address)?;
-    USERNAMES.insert(deps.storage, &admin_canonical_address, &msg.username)?;
+        ADMIN.save(deps.storage, &admin_canonical_address)?;
+        set_username(&admin_canonical_address, &msg.username, deps.storage)?;
+    }
 
     LOBBY_CONFIG.save(deps.storage, &lobby_config)?;
     REVEALED_CARDS.save(deps.storage, &0)?;
     IS_STARTED.save(deps.storage, &false)?;
     POT.save(deps.storage, &0)?;
+    PRIZE_POOL.save(deps.storage, &0)?;
 
     Ok(Response::default())
 }
 
-#[entry_point]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::StartGame {} => try_start_game(deps, info.sender, &env),
-        ExecuteMsg::BuyIn { username } => try_buy_in(username, deps, info.sender, info.funds),
-        ExecuteMsg::PlaceBet { value } => try_place_bet(info.sender, value.into(), deps, &env),
-        ExecuteMsg::Withdraw {} => try_withdraw_chips(info.sender, deps),
+        ExecuteMsg::BuyIn { username } => try_buy_in(username, deps, info.sender, info.funds, &env),
+        ExecuteMsg::Fold {} => try_fold(info.sender, deps, &env),
+        ExecuteMsg::Check {} => try_check(info.sender, deps, &env),
+        ExecuteMsg::Call {} => try_call(info.sender, deps, &env),
+        ExecuteMsg::Raise { to } => try_raise(info.sender, to.into(), deps, &env),
+        ExecuteMsg::AllIn {} => try_all_in(info.sender, deps, &env),
+        ExecuteMsg::ClaimTimeout {} => try_claim_timeout(info.sender, deps, &env),
+        ExecuteMsg::SitOut {} => try_sit_out(info.sender, deps),
+        ExecuteMsg::SitIn {} => try_sit_in(info.sender, deps, &env),
+        ExecuteMsg::SetStraddle { straddle } => try_set_straddle(info.sender, straddle, deps),
+        ExecuteMsg::SetShowUncontested { show } => {
+            try_set_show_uncontested(info.sender, show, deps)
+        }
+        ExecuteMsg::Withdraw {} => try_withdraw_chips(info.sender, deps, &env),
+        ExecuteMsg::SeatPlayer {
+            address,
+            username,
+            stack,
+        } => try_seat_player(info.sender, address, username, stack.u128(), deps),
+        ExecuteMsg::UnseatPlayer { address } => try_unseat_player(info.sender, address, deps),
+        ExecuteMsg::SetBigBlind { big_blind } => try_set_big_blind(info.sender, big_blind, deps),
     }
 }
 
-#[entry_point]
-pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+#[cfg_attr(not(feature = "library"), cosmwasm_std::entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::ViewPreStartState {} => query_pre_start_state(deps),
         QueryMsg::ViewGameState { permit } => query_game_state(deps, env, permit),
+        QueryMsg::HandHistory {
+            hand_id,
+            permit,
+            page,
+            page_size,
+        } => query_hand_history(deps, env, hand_id, permit, page, page_size),
+        QueryMsg::LastShowdown { permit } => query_last_showdown(deps, env, permit),
+        QueryMsg::SpectateGame {} => query_spectator_state(deps),
+        QueryMsg::VerifyHand { hand_id } => query_verify_hand(deps, hand_id),
     }
 }
@@ -1,35 +1,62 @@
 use cosmwasm_std::{
-    Addr, BankMsg, Coin, CosmosMsg, DepsMut, Env, Response, StdError, StdResult, Uint128,
+    Addr, BankMsg, CanonicalAddr, Coin, CosmosMsg, DepsMut, Env, Response, StdResult, Storage,
+    Uint128,
 };
 
 use crate::{
-    poker::{find_next_player_lazy, new_round, next_play, take_bet},
+    error::ContractError,
+    msg::TournamentConfig,
+    poker::{
+        award_uncontested_pot,
+        betting::check_bet_size,
+        count_active_players, end_turn, fold_hand,
+        history::{record_event, username},
+        mark_acted, new_round, reopen_action, take_bet,
+    },
     state::{
-        ADMIN, ALL_PLAYERS, BALANCES, BETS, BUTTON_POSITION, CURRENT_MIN_BET,
-        CURRENT_TURN_POSITION, HANDS, IS_STARTED, LOBBY_CONFIG, USERNAMES,
+        is_seat_vacant, is_valid_username, HandEvent, ADMIN, ALL_PLAYERS, BALANCES, BETS,
+        BIG_BLIND_POSITION, CONTRIBUTIONS, CURRENT_MIN_BET, CURRENT_TURN_POSITION, DIRECTOR, HANDS,
+        HAS_ACTED, IS_STARTED, LAST_RAISE_SIZE, LOBBY_CONFIG, MAX_SEATS, PENDING_BIG_BLIND,
+        PENDING_BUY_INS, PRIZES, PRIZE_POOL, SHOWS_UNCONTESTED, SITTING_OUT, STRADDLERS,
+        STREET_RAISES, TIMEOUTS, TOURNAMENT_STARTED_AT, TOURNAMENT_WINNER, TURN_STARTED_AT,
+        USERNAMES, USERNAME_OWNERS,
     },
 };
 
-pub fn try_start_game(deps: DepsMut, sender: Addr, env: &Env) -> StdResult<Response> {
+/// Number of consecutive timeouts after which a player is automatically sat out.
+const TIMEOUTS_BEFORE_SITTING_OUT: u8 = 2;
+
+pub fn try_start_game(deps: DepsMut, sender: Addr, env: &Env) -> Result<Response, ContractError> {
     if IS_STARTED.load(deps.storage)? {
-        return Err(StdError::generic_err("The game has already started"));
+        return Err(ContractError::GameAlreadyStarted);
     }
 
     let admin = deps.api.addr_humanize(&ADMIN.load(deps.storage)?)?;
     if sender != admin {
-        return Err(StdError::generic_err(
-            "Only the person who created the lobby can start the game",
-        ));
+        return Err(ContractError::Unauthorized);
+    }
+
+    if TOURNAMENT_WINNER.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::TournamentFinished);
     }
 
-    if ALL_PLAYERS.get_len(deps.storage)? < 2 {
-        return Err(StdError::generic_err("Insufficient number of players"));
+    // Every place in a tournament's payout table needs a player to finish in it.
+    let tournament = LOBBY_CONFIG.load(deps.storage)?.tournament;
+    let min_players = tournament
+        .as_ref()
+        .map_or(2, |tournament| tournament.payouts.len().max(2));
+    if count_active_players(deps.storage)? < min_players {
+        return Err(ContractError::NotEnoughPlayers);
     }
 
-    new_round(0, deps.storage, env)?;
+    if tournament.is_some() {
+        TOURNAMENT_STARTED_AT.save(deps.storage, &env.block.time.seconds())?;
+    }
 
     IS_STARTED.save(deps.storage, &true)?;
 
+    new_round(deps.storage, env)?;
+
     Ok(Response::default())
 }
 
@@ -38,162 +65,729 @@ pub fn try_buy_in(
     deps: DepsMut,
     sender: Addr,
     funds: Vec<Coin>,
-) -> StdResult<Response> {
-    if IS_STARTED.load(deps.storage)? {
-        return Err(StdError::generic_err("The game has already started"));
-    }
-
-    if ALL_PLAYERS.get_len(deps.storage)? >= 9 {
-        return Err(StdError::generic_err(
-            "There are already the maximum number of people in this lobby",
-        ));
+    env: &Env,
+) -> Result<Response, ContractError> {
+    if DIRECTOR.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::SeatedByDirector);
     }
 
-    // TODO: Check if the username is already taken.
-
     let sender = deps.api.addr_canonicalize(sender.as_str())?;
 
-    if BALANCES.get(deps.storage, &sender).is_some() {
-        return Err(StdError::generic_err("You have already bought in!"));
-    }
-
     if funds.len() != 1 {
-        return Err(StdError::generic_err("Only SCRT is accepted"));
+        return Err(ContractError::InvalidDenom);
     }
 
     if funds[0].denom != "uscrt" {
-        return Err(StdError::generic_err("Only SCRT is accepted"));
+        return Err(ContractError::InvalidDenom);
     }
 
     let buy_in_amount = funds[0].amount.u128();
 
     let lobby_config = LOBBY_CONFIG.load(deps.storage)?;
+    if let Some(tournament) = lobby_config.tournament {
+        return register_for_tournament(username, &tournament, buy_in_amount, &sender, deps);
+    }
+
     let min_buy_in = lobby_config.min_buy_in_bb as u32 * lobby_config.big_blind;
     let max_buy_in = lobby_config.max_buy_in_bb as u32 * lobby_config.big_blind;
 
-    if buy_in_amount < min_buy_in as u128 {
-        return Err(StdError::generic_err(format!(
-            "You must buy in with at least {min_buy_in} uSCRT"
-        )));
+    // Rebuys and top ups are limited by the chips the player already has, including those they
+    // have put into the current hand.
+    let balance = BALANCES.get(deps.storage, &sender).unwrap_or(0)
+        + PENDING_BUY_INS.get(deps.storage, &sender).unwrap_or(0)
+        + CONTRIBUTIONS.get(deps.storage, &sender).unwrap_or(0);
+    let new_balance = balance + buy_in_amount;
+
+    if new_balance < min_buy_in as u128 {
+        return Err(ContractError::BuyInBelowMinimum {
+            minimum: min_buy_in as u128 - balance,
+        });
+    }
+
+    if new_balance > max_buy_in as u128 {
+        return Err(ContractError::BuyInAboveMaximum {
+            maximum: (max_buy_in as u128).saturating_sub(balance),
+        });
+    }
+
+    // Players who already have a seat keep their username.
+    let has_seat = ALL_PLAYERS
+        .iter(deps.storage)?
+        .flatten()
+        .any(|address| address == sender);
+    if !has_seat {
+        validate_username(&username, &sender, deps.storage)?;
+        take_seat(&sender, deps.storage)?;
+        set_username(&sender, &username, deps.storage)?;
+    }
+
+    // Chips bought once the game has started are added at the start of the next hand.
+    if IS_STARTED.load(deps.storage)? {
+        let pending_amount = PENDING_BUY_INS.get(deps.storage, &sender).unwrap_or(0);
+        PENDING_BUY_INS.insert(deps.storage, &sender, &(pending_amount + buy_in_amount))?;
+    } else {
+        BALANCES.insert(deps.storage, &sender, &new_balance)?;
+        resume_paused_game(deps.storage, env)?;
+    }
+
+    Ok(Response::default())
+}
+
+/// Enter the player into the tournament, exchanging the buy in for the starting stack.
+fn register_for_tournament(
+    username: String,
+    tournament: &TournamentConfig,
+    buy_in_amount: u128,
+    sender: &CanonicalAddr,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    if TOURNAMENT_WINNER.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::TournamentFinished);
+    }
+
+    if IS_STARTED.load(deps.storage)? {
+        return Err(ContractError::GameAlreadyStarted);
+    }
+
+    if buy_in_amount != tournament.buy_in.u128() {
+        return Err(ContractError::TournamentBuyIn {
+            buy_in: tournament.buy_in.u128(),
+        });
     }
 
-    if buy_in_amount > max_buy_in as u128 {
-        return Err(StdError::generic_err(format!(
-            "You must buy in with at most {max_buy_in} uSCRT"
-        )));
+    if BALANCES.contains(deps.storage, sender) {
+        return Err(ContractError::AlreadyRegistered);
     }
 
-    ALL_PLAYERS.push(deps.storage, &sender)?;
-    BALANCES.insert(deps.storage, &sender, &buy_in_amount)?;
-    USERNAMES.insert(deps.storage, &sender, &username)?;
+    validate_username(&username, sender, deps.storage)?;
+    take_seat(sender, deps.storage)?;
+    set_username(sender, &username, deps.storage)?;
+
+    BALANCES.insert(deps.storage, sender, &(tournament.starting_stack as u128))?;
+    PRIZE_POOL.update(deps.storage, |prize_pool| Ok(prize_pool + buy_in_amount))?;
 
     Ok(Response::default())
 }
 
-pub fn try_place_bet(sender: Addr, value: u128, deps: DepsMut, env: &Env) -> StdResult<Response> {
+/// Check that the username follows the naming rules, and is not taken by anyone else.
+fn validate_username(
+    username: &str,
+    player_address: &CanonicalAddr,
+    storage: &dyn Storage,
+) -> Result<(), ContractError> {
+    if !is_valid_username(username) {
+        return Err(ContractError::InvalidUsername);
+    }
+
+    match USERNAME_OWNERS.get(storage, &username.to_lowercase()) {
+        Some(owner) if owner != *player_address => Err(ContractError::UsernameTaken {
+            username: username.to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Give the player a username, releasing any username they previously held.
+pub fn set_username(
+    player_address: &CanonicalAddr,
+    username: &str,
+    storage: &mut dyn Storage,
+) -> Result<(), ContractError> {
+    validate_username(username, player_address, storage)?;
+    remove_username(player_address, storage)?;
+
+    USERNAMES.insert(storage, player_address, &username.to_string())?;
+    USERNAME_OWNERS.insert(storage, &username.to_lowercase(), player_address)?;
+
+    Ok(())
+}
+
+/// Release the player's username, so that someone else may take it.
+fn remove_username(player_address: &CanonicalAddr, storage: &mut dyn Storage) -> StdResult<()> {
+    if let Some(username) = USERNAMES.get(storage, player_address) {
+        USERNAME_OWNERS.remove(storage, &username.to_lowercase())?;
+        USERNAMES.remove(storage, player_address)?;
+    }
+
+    Ok(())
+}
+
+/// Seat a new player, either in a seat left empty by a player who has withdrawn or busted, or in
+/// a new seat at the end of the table. A player who still holds a seat, such as one who withdrew
+/// and is rejoining, keeps it.
+fn take_seat(
+    player_address: &CanonicalAddr,
+    storage: &mut dyn Storage,
+) -> Result<(), ContractError> {
+    let has_seat = ALL_PLAYERS
+        .iter(storage)?
+        .flatten()
+        .any(|address| address == *player_address);
+    if has_seat {
+        return Ok(());
+    }
+
+    let vacant_seat = ALL_PLAYERS
+        .iter(storage)?
+        .flatten()
+        .enumerate()
+        .find(|(_, address)| is_seat_vacant(address, storage));
+
+    if let Some((position, previous_address)) = vacant_seat {
+        remove_username(&previous_address, storage)?;
+        SITTING_OUT.remove(storage, &previous_address)?;
+        STRADDLERS.remove(storage, &previous_address)?;
+        SHOWS_UNCONTESTED.remove(storage, &previous_address)?;
+        TIMEOUTS.remove(storage, &previous_address)?;
+        return Ok(ALL_PLAYERS.set_at(storage, position as u32, player_address)?);
+    }
+
+    if ALL_PLAYERS.get_len(storage)? >= MAX_SEATS {
+        return Err(ContractError::TableFull);
+    }
+
+    Ok(ALL_PLAYERS.push(storage, player_address)?)
+}
+
+/// Deal a new hand if the game was paused for lack of players, and there are now enough players
+/// to continue.
+fn resume_paused_game(storage: &mut dyn Storage, env: &Env) -> Result<(), ContractError> {
+    if BIG_BLIND_POSITION.may_load(storage)?.is_some() && count_active_players(storage)? >= 2 {
+        IS_STARTED.save(storage, &true)?;
+        new_round(storage, env)?;
+    }
+
+    Ok(())
+}
+
+/// Check that the game has started and that it is the sender's turn to act. Returns the sender's
+/// canonical address, chip balance, and position at the table. Acting clears the sender's count
+/// of consecutive timeouts.
+fn load_current_player(
+    sender: &Addr,
+    deps: DepsMut,
+) -> Result<(CanonicalAddr, u128, u8), ContractError> {
     if !IS_STARTED.load(deps.storage)? {
-        return Err(StdError::generic_err("The game has not started yet!"));
+        return Err(ContractError::GameNotStarted);
     }
 
     let sender = deps.api.addr_canonicalize(sender.as_str())?;
     let Some(players_balance) = BALANCES.get(deps.storage, &sender) else {
-        return Err(StdError::generic_err("You are not bought in!"));
+        return Err(ContractError::NotBoughtIn);
     };
 
     let current_turn_position = CURRENT_TURN_POSITION.load(deps.storage)?;
     if ALL_PLAYERS.get_at(deps.storage, current_turn_position as u32)? != sender {
-        return Err(StdError::generic_err("It is not your turn to bet"));
+        return Err(ContractError::NotYourTurn);
+    }
+
+    TIMEOUTS.remove(deps.storage, &sender)?;
+
+    Ok((sender, players_balance, current_turn_position))
+}
+
+pub fn try_fold(sender: Addr, mut deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
+    let (sender, _, current_turn_position) = load_current_player(&sender, deps.branch())?;
+
+    fold_hand(&sender, current_turn_position, deps.storage, env)?;
+
+    Ok(Response::default())
+}
+
+pub fn try_check(sender: Addr, mut deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
+    let (sender, _, current_turn_position) = load_current_player(&sender, deps.branch())?;
+
+    let min_bet = CURRENT_MIN_BET.load(deps.storage)?;
+    let previous_bet_amount = BETS.get(deps.storage, &sender).unwrap_or(0);
+
+    if previous_bet_amount < min_bet {
+        return Err(ContractError::CannotCheck {
+            to_call: min_bet - previous_bet_amount,
+        });
     }
 
-    if value > players_balance {
-        return Err(StdError::generic_err(
-            "You do not have that many chips to bet with",
-        ));
+    let player = username(&sender, deps.storage);
+    record_event(&HandEvent::Check { player }, deps.storage)?;
+
+    mark_acted(&sender, deps.storage)?;
+    end_turn(current_turn_position, deps.storage, env)?;
+
+    Ok(Response::default())
+}
+
+pub fn try_call(sender: Addr, mut deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
+    let (sender, players_balance, current_turn_position) =
+        load_current_player(&sender, deps.branch())?;
+
+    let min_bet = CURRENT_MIN_BET.load(deps.storage)?;
+    let previous_bet_amount = BETS.get(deps.storage, &sender).unwrap_or(0);
+
+    if previous_bet_amount >= min_bet {
+        return Err(ContractError::NothingToCall);
     }
 
-    let mut min_bet = CURRENT_MIN_BET.load(deps.storage)?;
+    // Calling with fewer chips than the bet puts the player all in, the excess is kept out of
+    // their reach when the pot is split into side pots.
+    let call_amount = (min_bet - previous_bet_amount).min(players_balance);
+    take_bet(
+        call_amount,
+        players_balance,
+        previous_bet_amount + call_amount,
+        &sender,
+        deps.storage,
+    )?;
+
+    let event = HandEvent::Call {
+        player: username(&sender, deps.storage),
+        amount: call_amount,
+        is_all_in: call_amount == players_balance,
+    };
+    record_event(&event, deps.storage)?;
+
+    mark_acted(&sender, deps.storage)?;
+    end_turn(current_turn_position, deps.storage, env)?;
+
+    Ok(Response::default())
+}
+
+pub fn try_raise(
+    sender: Addr,
+    to: u128,
+    mut deps: DepsMut,
+    env: &Env,
+) -> Result<Response, ContractError> {
+    let (sender, players_balance, current_turn_position) =
+        load_current_player(&sender, deps.branch())?;
+
+    let min_bet = CURRENT_MIN_BET.load(deps.storage)?;
     let previous_bet_amount = BETS.get(deps.storage, &sender).unwrap_or(0);
 
-    if value == 0 {
-        if previous_bet_amount < min_bet {
-            HANDS.remove(deps.storage, &sender)?;
-        }
-    } else {
-        let total_bet = previous_bet_amount + value;
+    // A raise smaller than the last full bet or raise is only allowed when it puts the player
+    // all in.
+    let min_raise_to = min_bet + LAST_RAISE_SIZE.load(deps.storage)?;
+    if to <= min_bet {
+        return Err(ContractError::BetBelowMinimum { min_raise_to });
+    }
+
+    if HAS_ACTED.contains(deps.storage, &sender) {
+        return Err(ContractError::ActionNotReopened);
+    }
+
+    let bet_amount = to - previous_bet_amount;
+    if bet_amount > players_balance {
+        return Err(ContractError::InsufficientChips);
+    }
+
+    let is_all_in = bet_amount == players_balance;
+    check_bet_size(to, min_bet, previous_bet_amount, is_all_in, deps.storage)?;
+
+    if to < min_raise_to && bet_amount < players_balance {
+        return Err(ContractError::BetBelowMinimum { min_raise_to });
+    }
+
+    record_raise(to, min_bet, deps.storage)?;
+    take_bet(bet_amount, players_balance, to, &sender, deps.storage)?;
+
+    let event = HandEvent::Raise {
+        player: username(&sender, deps.storage),
+        to,
+        is_all_in: bet_amount == players_balance,
+    };
+    record_event(&event, deps.storage)?;
+
+    mark_acted(&sender, deps.storage)?;
+    end_turn(current_turn_position, deps.storage, env)?;
+
+    Ok(Response::default())
+}
+
+pub fn try_all_in(sender: Addr, mut deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
+    let (sender, players_balance, current_turn_position) =
+        load_current_player(&sender, deps.branch())?;
+
+    let min_bet = CURRENT_MIN_BET.load(deps.storage)?;
+    let previous_bet_amount = BETS.get(deps.storage, &sender).unwrap_or(0);
+    let total_bet = previous_bet_amount + players_balance;
 
-        if total_bet < min_bet {
-            return Err(StdError::generic_err(
-                "Your total bet for this round does not meet the minimum bet",
-            ));
+    if total_bet > min_bet {
+        if HAS_ACTED.contains(deps.storage, &sender) {
+            return Err(ContractError::ActionNotReopened);
         }
 
-        if total_bet > min_bet {
-            min_bet = total_bet;
-            CURRENT_MIN_BET.save(deps.storage, &min_bet)?;
+        check_bet_size(total_bet, min_bet, previous_bet_amount, true, deps.storage)?;
+        record_raise(total_bet, min_bet, deps.storage)?;
+    }
+
+    take_bet(
+        players_balance,
+        players_balance,
+        total_bet,
+        &sender,
+        deps.storage,
+    )?;
+
+    let player = username(&sender, deps.storage);
+    let event = if total_bet > min_bet {
+        HandEvent::Raise {
+            player,
+            to: total_bet,
+            is_all_in: true,
+        }
+    } else {
+        HandEvent::Call {
+            player,
+            amount: players_balance,
+            is_all_in: true,
         }
+    };
+    record_event(&event, deps.storage)?;
+
+    mark_acted(&sender, deps.storage)?;
+    end_turn(current_turn_position, deps.storage, env)?;
+
+    Ok(Response::default())
+}
+
+/// Raise the current bet to `to`. Only a full raise, at least as large as the last full bet or
+/// raise, reopens the action to players who have already acted.
+fn record_raise(to: u128, min_bet: u128, storage: &mut dyn Storage) -> StdResult<()> {
+    let raise_size = to - min_bet;
+
+    if min_bet > 0 {
+        let street_raises = STREET_RAISES.load(storage)?;
+        STREET_RAISES.save(storage, &(street_raises + 1))?;
+    }
+
+    if raise_size >= LAST_RAISE_SIZE.load(storage)? {
+        LAST_RAISE_SIZE.save(storage, &raise_size)?;
+        reopen_action(storage)?;
+    }
+
+    CURRENT_MIN_BET.save(storage, &to)
+}
+
+pub fn try_claim_timeout(
+    sender: Addr,
+    deps: DepsMut,
+    env: &Env,
+) -> Result<Response, ContractError> {
+    if !IS_STARTED.load(deps.storage)? {
+        return Err(ContractError::GameNotStarted);
+    }
 
-        take_bet(value, players_balance, total_bet, &sender, deps.storage)?;
+    let sender = deps.api.addr_canonicalize(sender.as_str())?;
+    if !USERNAMES.contains(deps.storage, &sender) {
+        return Err(ContractError::NotAPlayer);
     }
 
-    let num_players = ALL_PLAYERS.get_len(deps.storage)? as u8;
+    let lobby_config = LOBBY_CONFIG.load(deps.storage)?;
+    let Some(turn_time_limit) = lobby_config.turn_time_limit else {
+        return Err(ContractError::NoTurnTimeLimit);
+    };
+
+    let deadline = TURN_STARTED_AT.load(deps.storage)? + turn_time_limit;
+    let now = env.block.time.seconds();
+    if now < deadline {
+        return Err(ContractError::TurnNotTimedOut {
+            seconds_left: deadline - now,
+        });
+    }
 
-    let (mut next_player_position, next_players_address) =
-        find_next_player_lazy((current_turn_position + 1) % num_players, deps.storage)?;
+    let current_turn_position = CURRENT_TURN_POSITION.load(deps.storage)?;
+    let stalled_player = ALL_PLAYERS.get_at(deps.storage, current_turn_position as u32)?;
+
+    // Players who repeatedly time out are sat out from the next hand onwards, except in
+    // tournaments, where they keep being dealt in and are blinded away instead.
+    let timeouts = TIMEOUTS.get(deps.storage, &stalled_player).unwrap_or(0) + 1;
+    TIMEOUTS.insert(deps.storage, &stalled_player, &timeouts)?;
+    if timeouts >= TIMEOUTS_BEFORE_SITTING_OUT && !is_tournament(deps.storage)? {
+        SITTING_OUT.insert(deps.storage, &stalled_player, &true)?;
+    }
 
-    let next_players_bet = BETS.get(deps.storage, &next_players_address).unwrap_or(0);
-    if next_players_bet == min_bet {
-        let mut left_of_dealer = (BUTTON_POSITION.load(deps.storage)? + 1) % num_players;
+    // The stalled player checks if they can, otherwise their hand is folded.
+    let previous_bet_amount = BETS.get(deps.storage, &stalled_player).unwrap_or(0);
+    if previous_bet_amount >= CURRENT_MIN_BET.load(deps.storage)? {
+        let player = username(&stalled_player, deps.storage);
+        record_event(&HandEvent::Check { player }, deps.storage)?;
 
-        if next_play(deps.storage)? {
-            new_round(left_of_dealer, deps.storage, env)?;
-            left_of_dealer = (left_of_dealer + 1) % num_players;
-        };
+        mark_acted(&stalled_player, deps.storage)?;
+        end_turn(current_turn_position, deps.storage, env)?;
+    } else {
+        fold_hand(&stalled_player, current_turn_position, deps.storage, env)?;
+    }
+
+    Ok(Response::default())
+}
+
+/// Whether the table is playing a tournament, either on its own or run by a director.
+fn is_tournament(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(LOBBY_CONFIG.load(storage)?.tournament.is_some() || DIRECTOR.may_load(storage)?.is_some())
+}
+
+pub fn try_sit_out(sender: Addr, deps: DepsMut) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_canonicalize(sender.as_str())?;
+    if !USERNAMES.contains(deps.storage, &sender) {
+        return Err(ContractError::NotAPlayer);
+    }
+
+    if SITTING_OUT.contains(deps.storage, &sender) {
+        return Err(ContractError::AlreadySittingOut);
+    }
+
+    if is_tournament(deps.storage)? {
+        return Err(ContractError::CannotSitOutOfTournament);
+    }
+
+    // A hand that is already in progress is played out as normal.
+    SITTING_OUT.insert(deps.storage, &sender, &true)?;
+
+    Ok(Response::default())
+}
+
+pub fn try_sit_in(sender: Addr, deps: DepsMut, env: &Env) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_canonicalize(sender.as_str())?;
+    if !USERNAMES.contains(deps.storage, &sender) {
+        return Err(ContractError::NotAPlayer);
+    }
+
+    if !SITTING_OUT.contains(deps.storage, &sender) {
+        return Err(ContractError::NotSittingOut);
+    }
+
+    if !BALANCES.contains(deps.storage, &sender) {
+        return Err(ContractError::NoChips);
+    }
+
+    SITTING_OUT.remove(deps.storage, &sender)?;
+    TIMEOUTS.remove(deps.storage, &sender)?;
+
+    if !IS_STARTED.load(deps.storage)? {
+        resume_paused_game(deps.storage, env)?;
+    }
+
+    Ok(Response::default())
+}
+
+pub fn try_set_straddle(
+    sender: Addr,
+    straddle: bool,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_canonicalize(sender.as_str())?;
+    if !USERNAMES.contains(deps.storage, &sender) {
+        return Err(ContractError::NotAPlayer);
+    }
+
+    if !LOBBY_CONFIG.load(deps.storage)?.allow_straddle {
+        return Err(ContractError::StraddleNotAllowed);
+    }
+
+    if straddle {
+        STRADDLERS.insert(deps.storage, &sender, &true)?;
+    } else {
+        STRADDLERS.remove(deps.storage, &sender)?;
+    }
+
+    Ok(Response::default())
+}
+
+pub fn try_set_show_uncontested(
+    sender: Addr,
+    show: bool,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    let sender = deps.api.addr_canonicalize(sender.as_str())?;
+    if !USERNAMES.contains(deps.storage, &sender) {
+        return Err(ContractError::NotAPlayer);
+    }
 
-        next_player_position = find_next_player_lazy(left_of_dealer, deps.storage)?.0;
+    if show {
+        SHOWS_UNCONTESTED.insert(deps.storage, &sender, &true)?;
+    } else {
+        SHOWS_UNCONTESTED.remove(deps.storage, &sender)?;
     }
-    CURRENT_TURN_POSITION.save(deps.storage, &next_player_position)?;
 
     Ok(Response::default())
 }
 
-pub fn try_withdraw_chips(sender: Addr, deps: DepsMut) -> StdResult<Response> {
+pub fn try_withdraw_chips(
+    sender: Addr,
+    deps: DepsMut,
+    env: &Env,
+) -> Result<Response, ContractError> {
     let canonical_address = deps.api.addr_canonicalize(sender.as_str())?;
-    let Some(balance) = BALANCES.get(deps.storage, &canonical_address) else {
-        return Err(StdError::generic_err("You are not part of this game"));
-    };
+    if let Some(tournament) = LOBBY_CONFIG.load(deps.storage)?.tournament {
+        return withdraw_from_tournament(sender, &canonical_address, &tournament, deps);
+    }
+
+    // Prizes of tournaments run by a director are paid out by the director.
+    if DIRECTOR.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::TournamentInProgress);
+    }
+
+    let balance = BALANCES.get(deps.storage, &canonical_address).unwrap_or(0)
+        + PENDING_BUY_INS
+            .get(deps.storage, &canonical_address)
+            .unwrap_or(0);
+    if balance == 0 {
+        return Err(ContractError::NotAPlayer);
+    }
 
-    if BETS.get(deps.storage, &canonical_address).is_some()
+    if CONTRIBUTIONS
+        .get(deps.storage, &canonical_address)
+        .is_some()
         && HANDS.get(deps.storage, &canonical_address).is_some()
     {
-        return Err(StdError::generic_err(
-            "You can only withdraw at the start of a new round, or after folding",
-        ));
+        return Err(ContractError::WithdrawDuringHand);
+    }
+
+    // Withdrawing before putting any chips in the pot folds the player's hand.
+    if HANDS.contains(deps.storage, &canonical_address) {
+        let player = username(&canonical_address, deps.storage);
+        record_event(&HandEvent::Fold { player }, deps.storage)?;
     }
 
+    // Any chips already bet stay in CONTRIBUTIONS, so they are still accounted for when the pot
+    // is split.
     BALANCES.remove(deps.storage, &canonical_address)?;
+    PENDING_BUY_INS.remove(deps.storage, &canonical_address)?;
     HANDS.remove(deps.storage, &canonical_address)?;
-    BETS.remove(deps.storage, &canonical_address)?;
+    SITTING_OUT.remove(deps.storage, &canonical_address)?;
+    TIMEOUTS.remove(deps.storage, &canonical_address)?;
 
-    let current_player_position = CURRENT_TURN_POSITION.load(deps.storage)?;
-    let current_player_address =
-        ALL_PLAYERS.get_at(deps.storage, current_player_position as u32)?;
-    let num_players = ALL_PLAYERS.get_len(deps.storage)? as u8;
+    if IS_STARTED.load(deps.storage)? {
+        let current_turn_position = CURRENT_TURN_POSITION.load(deps.storage)?;
+
+        if award_uncontested_pot(deps.storage)? {
+            new_round(deps.storage, env)?;
+        } else if ALL_PLAYERS.get_at(deps.storage, current_turn_position as u32)?
+            == canonical_address
+        {
+            end_turn(current_turn_position, deps.storage, env)?;
+        }
+    }
 
-    if current_player_address == canonical_address {
-        let next_player_position =
-            find_next_player_lazy((current_player_position + 1) % num_players, deps.storage)?.0;
-        CURRENT_TURN_POSITION.save(deps.storage, &next_player_position)?;
+    Ok(Response::new().add_message(send_uscrt(sender, balance)))
+}
+
+/// Collect the player's prize once the tournament is over, or take back their buy in if they
+/// leave before it starts. Tournament chips are not worth any uSCRT themselves.
+fn withdraw_from_tournament(
+    sender: Addr,
+    canonical_address: &CanonicalAddr,
+    tournament: &TournamentConfig,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    if TOURNAMENT_WINNER.may_load(deps.storage)?.is_some() {
+        let Some(prize) = PRIZES.get(deps.storage, canonical_address) else {
+            return Err(ContractError::NoPrize);
+        };
+        PRIZES.remove(deps.storage, canonical_address)?;
+
+        return Ok(Response::new().add_message(send_uscrt(sender, prize)));
     }
 
+    if IS_STARTED.load(deps.storage)? {
+        return Err(ContractError::TournamentInProgress);
+    }
+
+    if !BALANCES.contains(deps.storage, canonical_address) {
+        return Err(ContractError::NotAPlayer);
+    }
+
+    let buy_in = tournament.buy_in.u128();
+
+    BALANCES.remove(deps.storage, canonical_address)?;
+    PRIZE_POOL.update(deps.storage, |prize_pool| Ok(prize_pool - buy_in))?;
+
+    Ok(Response::new().add_message(send_uscrt(sender, buy_in)))
+}
+
+fn send_uscrt(recipient: Addr, amount: u128) -> CosmosMsg {
     let coins_to_send: Vec<Coin> = vec![Coin {
         denom: "uscrt".to_string(),
-        amount: Uint128::from(balance),
+        amount: Uint128::from(amount),
     }];
 
-    let message = CosmosMsg::Bank(BankMsg::Send {
-        to_address: sender.clone().into_string(),
+    CosmosMsg::Bank(BankMsg::Send {
+        to_address: recipient.into_string(),
         amount: coins_to_send,
-    });
+    })
+}
+
+/// Check that the sender is the tournament director running this table.
+fn ensure_director(sender: &Addr, deps: &DepsMut) -> Result<(), ContractError> {
+    let Some(director) = DIRECTOR.may_load(deps.storage)? else {
+        return Err(ContractError::NotDirector);
+    };
+
+    if deps.api.addr_canonicalize(sender.as_str())? != director {
+        return Err(ContractError::NotDirector);
+    }
+
+    Ok(())
+}
+
+pub fn try_seat_player(
+    sender: Addr,
+    address: String,
+    username: String,
+    stack: u128,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    ensure_director(&sender, &deps)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let player = deps.api.addr_canonicalize(address.as_str())?;
+
+    let has_seat = ALL_PLAYERS
+        .iter(deps.storage)?
+        .flatten()
+        .any(|address| address == player);
+    if !has_seat {
+        validate_username(&username, &player, deps.storage)?;
+        take_seat(&player, deps.storage)?;
+        set_username(&player, &username, deps.storage)?;
+    }
+
+    // A newly seated player is not dealt into a hand already in progress, so their chips can be
+    // added straight away.
+    let balance = BALANCES.get(deps.storage, &player).unwrap_or(0);
+    BALANCES.insert(deps.storage, &player, &(balance + stack))?;
+
+    Ok(Response::default())
+}
 
-    Ok(Response::new().add_message(message))
+pub fn try_unseat_player(
+    sender: Addr,
+    address: String,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    ensure_director(&sender, &deps)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let player = deps.api.addr_canonicalize(address.as_str())?;
+
+    if HANDS.contains(deps.storage, &player) || CONTRIBUTIONS.contains(deps.storage, &player) {
+        return Err(ContractError::PlayerInHand);
+    }
+
+    // The director moves the player's chips to their new table, leaving their seat empty.
+    BALANCES.remove(deps.storage, &player)?;
+    SITTING_OUT.remove(deps.storage, &player)?;
+    TIMEOUTS.remove(deps.storage, &player)?;
+
+    Ok(Response::default())
+}
+
+pub fn try_set_big_blind(
+    sender: Addr,
+    big_blind: u32,
+    deps: DepsMut,
+) -> Result<Response, ContractError> {
+    ensure_director(&sender, &deps)?;
+
+    PENDING_BIG_BLIND.save(deps.storage, &big_blind)?;
+
+    Ok(Response::default())
 }
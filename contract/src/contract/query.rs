@@ -1,30 +1,81 @@
-use cosmwasm_std::{to_binary, Binary, CanonicalAddr, Deps, Env, StdError, StdResult};
+use cosmwasm_std::{to_binary, Binary, CanonicalAddr, Deps, Env, StdError};
 use secret_toolkit::permit::Permit;
 
+use crate::error::ContractError;
+use crate::msg::TournamentConfig;
+use crate::poker::history::{hand_history, username};
+use crate::poker::tournament::{current_big_blind, prizes};
 use crate::state::{
-    get_balances, GameState, PreStartState, ADMIN, ALL_PLAYERS, BETS, BUTTON_POSITION,
-    CURRENT_MIN_BET, CURRENT_TURN_POSITION, HANDS, IS_STARTED, LOBBY_CONFIG, POT, REVEALED_CARDS,
-    TABLE, USERNAMES,
+    card_commitment, card_commitments, card_salt, deck_commitment, get_balances, is_seat_vacant,
+    GameState, HandEvent, HandHistoryResponse, PreStartState, RevealedCard, SpectatorState,
+    TournamentState, VerifyHandResponse, ADMIN, ALL_PLAYERS, BETS, BLIND_LEVEL, BUTTON_POSITION,
+    CONTRIBUTIONS, CURRENT_MIN_BET, CURRENT_TURN_POSITION, DECK_PROOFS, ELIMINATED, HANDS, HAND_ID,
+    IS_STARTED, LAST_RAISE_SIZE, LAST_SHOWDOWN, LOBBY_CONFIG, POT, PRIZE_POOL, REVEALED_CARDS,
+    SITTING_OUT, TABLE, USERNAMES,
 };
 
-pub fn query_pre_start_state(deps: Deps) -> StdResult<Binary> {
+pub fn query_pre_start_state(deps: Deps) -> Result<Binary, ContractError> {
     let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(deps.storage)?.flatten().collect();
+    let lobby_config = LOBBY_CONFIG.load(deps.storage)?;
+
+    let tournament = match lobby_config.tournament {
+        Some(ref tournament) => Some(query_tournament_state(tournament, deps)?),
+        None => None,
+    };
 
     let pre_start_state = PreStartState {
         admin: USERNAMES
             .get(deps.storage, &ADMIN.load(deps.storage)?)
-            .unwrap(),
-        lobby_config: LOBBY_CONFIG.load(deps.storage)?,
+            .unwrap_or_default(),
+        lobby_config,
         is_started: IS_STARTED.load(deps.storage)?,
         balances: get_balances(&players, deps),
+        occupied_seats: players
+            .iter()
+            .filter(|address| !is_seat_vacant(address, deps.storage))
+            .count() as u8,
+        players_in_hand: players
+            .iter()
+            .filter(|address| {
+                HANDS.contains(deps.storage, address)
+                    || CONTRIBUTIONS.contains(deps.storage, address)
+            })
+            .map(|address| username(address, deps.storage))
+            .collect(),
+        tournament,
     };
 
-    to_binary(&pre_start_state)
+    Ok(to_binary(&pre_start_state)?)
+}
+
+fn query_tournament_state(
+    tournament: &TournamentConfig,
+    deps: Deps,
+) -> Result<TournamentState, ContractError> {
+    let eliminated = ELIMINATED
+        .iter(deps.storage)?
+        .flatten()
+        .map(|address| username(&address, deps.storage))
+        .collect();
+
+    // Prizes stay listed after they have been withdrawn.
+    let prizes = prizes(tournament, deps.storage)?
+        .into_iter()
+        .map(|(address, prize)| (username(&address, deps.storage), prize))
+        .collect();
+
+    Ok(TournamentState {
+        prize_pool: PRIZE_POOL.load(deps.storage)?,
+        blind_level: BLIND_LEVEL.may_load(deps.storage)?.unwrap_or(0),
+        big_blind: current_big_blind(deps.storage)?,
+        eliminated,
+        prizes,
+    })
 }
 
-pub fn query_game_state(deps: Deps, env: Env, permit: Permit) -> StdResult<Binary> {
+pub fn query_game_state(deps: Deps, env: Env, permit: Permit) -> Result<Binary, ContractError> {
     if !IS_STARTED.load(deps.storage)? {
-        return Err(StdError::generic_err("The game has not yet started"));
+        return Err(ContractError::GameNotStarted);
     }
 
     let account = secret_toolkit::permit::validate(
@@ -37,12 +88,50 @@ pub fn query_game_state(deps: Deps, env: Env, permit: Permit) -> StdResult<Binar
     let sender = deps.api.addr_canonicalize(&account)?;
 
     if !USERNAMES.contains(deps.storage, &sender) {
-        return Err(StdError::generic_err("You are not part of this game"));
+        return Err(ContractError::NotAPlayer);
     }
 
     let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(deps.storage)?.flatten().collect();
     let balances = get_balances(&players, deps);
 
+    let (current_turn, button_player) = current_turn_and_button(&balances, deps)?;
+
+    let current_bet = CURRENT_MIN_BET.load(deps.storage)?;
+    let bet = BETS.get(deps.storage, &sender).unwrap_or(0);
+    let min_raise_to = current_bet + LAST_RAISE_SIZE.load(deps.storage)?;
+
+    let sitting_out = players
+        .iter()
+        .filter(|address| SITTING_OUT.contains(deps.storage, address))
+        .filter_map(|address| USERNAMES.get(deps.storage, address))
+        .collect();
+
+    let all_state = GameState {
+        balances,
+        table: TABLE
+            .iter(deps.storage)?
+            .take(REVEALED_CARDS.load(deps.storage)? as usize)
+            .flatten()
+            .collect(),
+        pot: POT.load(deps.storage)?,
+        hand_id: HAND_ID.load(deps.storage)?,
+        hand: HANDS.get(deps.storage, &sender),
+        current_turn,
+        button_player,
+        min_bet: current_bet - bet,
+        bet,
+        min_raise_to,
+        sitting_out,
+    };
+
+    Ok(to_binary(&all_state)?)
+}
+
+/// The usernames of the player whose turn it is and the player on the button.
+fn current_turn_and_button(
+    balances: &[(String, u128)],
+    deps: Deps,
+) -> Result<(String, String), ContractError> {
     let current_turn_position = CURRENT_TURN_POSITION.load(deps.storage)? as usize;
     let current_turn = balances
         .get(current_turn_position)
@@ -63,10 +152,38 @@ pub fn query_game_state(deps: Deps, env: Env, permit: Permit) -> StdResult<Binar
         .0
         .clone();
 
-    let min_bet =
-        CURRENT_MIN_BET.load(deps.storage)? - BETS.get(deps.storage, &sender).unwrap_or(0);
+    Ok((current_turn, button_player))
+}
 
-    let all_state = GameState {
+pub fn query_spectator_state(deps: Deps) -> Result<Binary, ContractError> {
+    if LOBBY_CONFIG.load(deps.storage)?.disable_spectators {
+        return Err(ContractError::SpectatorsDisabled);
+    }
+
+    if !IS_STARTED.load(deps.storage)? {
+        return Err(ContractError::GameNotStarted);
+    }
+
+    let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(deps.storage)?.flatten().collect();
+    let balances = get_balances(&players, deps);
+    let (current_turn, button_player) = current_turn_and_button(&balances, deps)?;
+
+    let hand_id = HAND_ID.load(deps.storage)?;
+    let last_action = hand_history(hand_id)
+        .iter(deps.storage)?
+        .rev()
+        .flatten()
+        .find(|event| {
+            matches!(
+                event,
+                HandEvent::Fold { .. }
+                    | HandEvent::Check { .. }
+                    | HandEvent::Call { .. }
+                    | HandEvent::Raise { .. }
+            )
+        });
+
+    let spectator_state = SpectatorState {
         balances,
         table: TABLE
             .iter(deps.storage)?
@@ -74,11 +191,122 @@ pub fn query_game_state(deps: Deps, env: Env, permit: Permit) -> StdResult<Binar
             .flatten()
             .collect(),
         pot: POT.load(deps.storage)?,
-        hand: HANDS.get(deps.storage, &sender),
+        hand_id,
         current_turn,
         button_player,
-        min_bet,
+        last_action,
+    };
+
+    Ok(to_binary(&spectator_state)?)
+}
+
+/// Number of hand history events returned per page when no page size is given.
+const DEFAULT_PAGE_SIZE: u32 = 50;
+
+pub fn query_hand_history(
+    deps: Deps,
+    env: Env,
+    hand_id: u64,
+    permit: Permit,
+    page: Option<u32>,
+    page_size: Option<u32>,
+) -> Result<Binary, ContractError> {
+    let account = secret_toolkit::permit::validate(
+        deps,
+        "revoked_permits",
+        &permit,
+        env.contract.address.to_string(),
+        None,
+    )?;
+    let sender = deps.api.addr_canonicalize(&account)?;
+
+    if !USERNAMES.contains(deps.storage, &sender) {
+        return Err(ContractError::NotAPlayer);
+    }
+
+    if hand_id == 0 || hand_id > HAND_ID.may_load(deps.storage)?.unwrap_or(0) {
+        return Err(ContractError::HandNotDealt { hand_id });
+    }
+
+    let history = hand_history(hand_id);
+    let response = HandHistoryResponse {
+        hand_id,
+        events: history.paging(
+            deps.storage,
+            page.unwrap_or(0),
+            page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+        )?,
+        total_events: history.get_len(deps.storage)?,
+    };
+
+    Ok(to_binary(&response)?)
+}
+
+pub fn query_last_showdown(deps: Deps, env: Env, permit: Permit) -> Result<Binary, ContractError> {
+    let account = secret_toolkit::permit::validate(
+        deps,
+        "revoked_permits",
+        &permit,
+        env.contract.address.to_string(),
+        None,
+    )?;
+    let sender = deps.api.addr_canonicalize(&account)?;
+
+    if !USERNAMES.contains(deps.storage, &sender) {
+        return Err(ContractError::NotAPlayer);
+    }
+
+    let last_showdown = LAST_SHOWDOWN
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoShowdown)?;
+
+    Ok(to_binary(&last_showdown)?)
+}
+
+pub fn query_verify_hand(deps: Deps, hand_id: u64) -> Result<Binary, ContractError> {
+    let proof = DECK_PROOFS
+        .get(deps.storage, &hand_id)
+        .ok_or(ContractError::HandNotDealt { hand_id })?;
+
+    let response = if proof.is_revealed {
+        let card_commitments = card_commitments(&proof.cards, &proof.salt);
+        let revealed_cards: Vec<RevealedCard> = proof
+            .seen_positions
+            .iter()
+            .map(|&position| RevealedCard {
+                position,
+                card: proof.cards[position as usize],
+                salt: card_salt(&proof.salt, position).to_vec().into(),
+            })
+            .collect();
+
+        let is_valid = deck_commitment(&card_commitments)[..] == proof.commitment[..]
+            && revealed_cards.iter().all(|revealed| {
+                card_commitment(revealed.card, &revealed.salt)
+                    == card_commitments[revealed.position as usize]
+            });
+
+        VerifyHandResponse {
+            hand_id,
+            commitment: proof.commitment.into(),
+            card_commitments: Some(
+                card_commitments
+                    .iter()
+                    .map(|commitment| commitment.to_vec().into())
+                    .collect(),
+            ),
+            revealed_cards: Some(revealed_cards),
+            is_valid: Some(is_valid),
+        }
+    } else {
+        VerifyHandResponse {
+            hand_id,
+            commitment: proof.commitment.into(),
+            card_commitments: None,
+            revealed_cards: None,
+            is_valid: None,
+        }
     };
 
-    to_binary(&all_state)
+    Ok(to_binary(&response)?)
 }
@@ -0,0 +1,129 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+use crate::state::{MAX_SEATS, MAX_USERNAME_LENGTH, MIN_USERNAME_LENGTH};
+
+/// Every way a message to the contract can fail. Errors are displayed as their code followed by
+/// a description, such as `not_your_turn: It is not your turn to bet`, so frontends can match on
+/// the code without depending on the wording of the description.
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("std_error: {0}")]
+    Std(#[from] StdError),
+    #[error("invalid_buy_in_range: Min buy in must be less than or equal to the max buy in")]
+    InvalidBuyInRange,
+    #[error("game_already_started: The game has already started")]
+    GameAlreadyStarted,
+    #[error("game_not_started: The game has not started yet")]
+    GameNotStarted,
+    #[error("unauthorized: Only the person who created the lobby can start the game")]
+    Unauthorized,
+    #[error("not_enough_players: Insufficient number of players")]
+    NotEnoughPlayers,
+    #[error("invalid_denom: Only SCRT is accepted")]
+    InvalidDenom,
+    #[error("buy_in_below_minimum: You must buy in with at least {minimum} uSCRT")]
+    BuyInBelowMinimum { minimum: u128 },
+    #[error("buy_in_above_maximum: You must buy in with at most {maximum} uSCRT")]
+    BuyInAboveMaximum { maximum: u128 },
+    #[error("table_full: There are already the maximum number of people in this lobby")]
+    TableFull,
+    #[error(
+        "invalid_username: Usernames must be {min} to {max} characters long, using only letters, \
+        numbers, underscores and hyphens",
+        min = MIN_USERNAME_LENGTH,
+        max = MAX_USERNAME_LENGTH
+    )]
+    InvalidUsername,
+    #[error("username_taken: The username {username} is already taken")]
+    UsernameTaken { username: String },
+    #[error("not_bought_in: You are not bought in")]
+    NotBoughtIn,
+    #[error("not_a_player: You are not part of this game")]
+    NotAPlayer,
+    #[error("not_your_turn: It is not your turn to bet")]
+    NotYourTurn,
+    #[error("cannot_check: You cannot check, you must call {to_call} uSCRT or fold")]
+    CannotCheck { to_call: u128 },
+    #[error("nothing_to_call: There is no bet to call, check instead")]
+    NothingToCall,
+    #[error("bet_below_minimum: You must raise to at least {min_raise_to} uSCRT")]
+    BetBelowMinimum { min_raise_to: u128 },
+    #[error(
+        "action_not_reopened: The action has not been reopened by a full raise, you may only call \
+        or fold"
+    )]
+    ActionNotReopened,
+    #[error("insufficient_chips: You do not have that many chips to bet with")]
+    InsufficientChips,
+    #[error("no_turn_time_limit: This lobby does not have a turn time limit")]
+    NoTurnTimeLimit,
+    #[error("turn_not_timed_out: The current player has {seconds_left} seconds left to act")]
+    TurnNotTimedOut { seconds_left: u64 },
+    #[error("already_sitting_out: You are already sitting out")]
+    AlreadySittingOut,
+    #[error("not_sitting_out: You are not sitting out")]
+    NotSittingOut,
+    #[error("no_chips: You have no chips to play with")]
+    NoChips,
+    #[error(
+        "withdraw_during_hand: You can only withdraw at the start of a new round, or after folding"
+    )]
+    WithdrawDuringHand,
+    #[error("hand_not_dealt: Hand {hand_id} has not been dealt")]
+    HandNotDealt { hand_id: u64 },
+    #[error("randomness_unavailable: Random source was unavailable")]
+    RandomnessUnavailable,
+    #[error(
+        "invalid_blind_schedule: Tournaments need at least one blind level, every big blind must \
+        be more than zero, and each level must last at least one hand or second"
+    )]
+    InvalidBlindSchedule,
+    #[error("invalid_starting_stack: The starting stack must be at least the first big blind")]
+    InvalidStartingStack,
+    #[error(
+        "invalid_payouts: The payout table must pay between 1 and {max_seats} places, with \
+        percentages adding up to 100",
+        max_seats = MAX_SEATS
+    )]
+    InvalidPayouts,
+    #[error("tournament_buy_in: The tournament buy in is exactly {buy_in} uSCRT")]
+    TournamentBuyIn { buy_in: u128 },
+    #[error("already_registered: You are already registered for this tournament")]
+    AlreadyRegistered,
+    #[error(
+        "tournament_in_progress: Chips cannot be withdrawn while the tournament is in progress"
+    )]
+    TournamentInProgress,
+    #[error("tournament_finished: The tournament has finished")]
+    TournamentFinished,
+    #[error(
+        "cannot_sit_out_of_tournament: Tournament players are dealt into every hand until they \
+        are eliminated"
+    )]
+    CannotSitOutOfTournament,
+    #[error("no_prize: You did not win a prize in this tournament")]
+    NoPrize,
+    #[error("not_director: Only the tournament director can do this")]
+    NotDirector,
+    #[error("player_in_hand: The player cannot leave the table in the middle of a hand")]
+    PlayerInHand,
+    #[error("seated_by_director: Players are seated at this table by the tournament director")]
+    SeatedByDirector,
+    #[error("invalid_ante: The ante must be at least 1 and no more than the big blind")]
+    InvalidAnte,
+    #[error("straddle_not_allowed: This lobby does not allow straddles")]
+    StraddleNotAllowed,
+    #[error(
+        "bet_above_maximum: You can raise to at most {max_raise_to} uSCRT in a pot limit game"
+    )]
+    BetAboveMaximum { max_raise_to: u128 },
+    #[error("fixed_bet_size: Bets in a fixed limit game must be to exactly {raise_to} uSCRT")]
+    FixedBetSize { raise_to: u128 },
+    #[error("raise_cap_reached: The betting round has been capped, so you can only call or fold")]
+    RaiseCapReached,
+    #[error("no_showdown: No hand has been completed at this table yet")]
+    NoShowdown,
+    #[error("spectators_disabled: Only players seated at this table can watch the game")]
+    SpectatorsDisabled,
+}
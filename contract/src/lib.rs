@@ -1,4 +1,8 @@
 pub mod contract;
+pub mod error;
 pub mod msg;
-pub mod state;
 pub mod poker;
+pub mod pokerstars;
+pub mod state;
+#[cfg(feature = "test-support")]
+pub mod test_support;
@@ -9,20 +9,172 @@ pub struct InstantiateMsg {
     pub big_blind: u32,
     pub max_buy_in_bb: u8,
     pub min_buy_in_bb: u8,
+    pub turn_time_limit: Option<u64>,
+    /// Address of the player who controls the lobby, defaults to the sender.
+    pub admin: Option<String>,
+    /// Play a sit-and-go tournament instead of a cash game.
+    pub tournament: Option<TournamentConfig>,
+    /// Address of a tournament director contract, which becomes the admin, seats and moves every
+    /// player, sets the blinds, and deals each hand. The director is not a player, so `username`
+    /// is unused.
+    pub director: Option<String>,
+    pub ante: Option<Ante>,
+    /// Let the player under the gun choose to straddle before the flop.
+    #[serde(default)]
+    pub allow_straddle: bool,
+    #[serde(default)]
+    pub betting_structure: BettingStructure,
+    #[serde(default)]
+    pub variant: GameVariant,
+    /// Only let players seated at the table watch the game.
+    #[serde(default)]
+    pub disable_spectators: bool,
+}
+
+/// The poker game dealt at the table.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GameVariant {
+    /// Texas Hold'em, where players make their best hand from two hole cards and the board.
+    #[default]
+    Holdem,
+    /// Omaha, where players are dealt four hole cards and must use exactly two of them with
+    /// exactly three cards from the board. Usually played pot limit.
+    Omaha,
+    /// Short-deck (6+) Hold'em, dealt from a 36 card deck without the twos through fives. A
+    /// flush beats a full house, and A-6-7-8-9 is the lowest straight.
+    ShortDeck,
+}
+
+impl GameVariant {
+    /// Number of hole cards dealt to each player.
+    pub fn num_hole_cards(self) -> usize {
+        match self {
+            GameVariant::Holdem | GameVariant::ShortDeck => 2,
+            GameVariant::Omaha => 4,
+        }
+    }
+}
+
+/// The limits on the size of each bet and raise.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BettingStructure {
+    /// Players may bet any amount up to their whole stack.
+    #[default]
+    NoLimit,
+    /// Players may raise by at most the size of the pot after calling.
+    PotLimit,
+    /// Every bet and raise is one big blind before the flop and on the flop, and two big blinds
+    /// on the turn and river, with at most `raise_cap` raises after the first bet of each round.
+    FixedLimit { raise_cap: u8 },
+}
+
+/// A forced bet posted before each hand, on top of the blinds.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Ante {
+    /// Every player dealt into the hand posts an ante of this many chips.
+    EveryPlayer(u32),
+    /// The player in the big blind posts a single ante of one big blind for the whole table.
+    BigBlind,
+}
+
+/// How long each blind level of a tournament lasts.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlindLevelDuration {
+    Hands(u32),
+    Seconds(u64),
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
+pub struct TournamentConfig {
+    /// uSCRT each player pays to enter, all of which goes into the prize pool.
+    pub buy_in: Uint128,
+    /// Chips each player starts the tournament with.
+    pub starting_stack: u32,
+    /// The big blind of each level in turn. The last level lasts until the tournament is over.
+    pub blind_levels: Vec<u32>,
+    pub level_duration: BlindLevelDuration,
+    /// Percentage of the prize pool paid to each finishing position, starting with first place.
+    pub payouts: Vec<u8>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     StartGame {},
-    BuyIn { username: String },
-    PlaceBet { value: Uint128 },
+    BuyIn {
+        username: String,
+    },
+    Fold {},
+    Check {},
+    Call {},
+    /// Raise the total bet for this betting round to `to` uSCRT, within the limits of the lobby's
+    /// betting structure.
+    Raise {
+        to: Uint128,
+    },
+    AllIn {},
+    /// Check or fold on behalf of the current player, once their turn time limit has passed.
+    ClaimTimeout {},
+    /// Keep your seat and chips, but stop being dealt into new hands.
+    SitOut {},
+    SitIn {},
     Withdraw {},
+    /// Choose whether to straddle, posting a blind raise to twice the big blind, whenever you are
+    /// under the gun. Only allowed in lobbies that allow straddles.
+    SetStraddle {
+        straddle: bool,
+    },
+    /// Choose whether to show your hand, rather than mucking it, whenever every other player
+    /// folds to you.
+    SetShowUncontested {
+        show: bool,
+    },
+    /// Seat a player with `stack` chips, taking effect from the next hand. Only the tournament
+    /// director may do this.
+    SeatPlayer {
+        address: String,
+        username: String,
+        stack: Uint128,
+    },
+    /// Remove a player and their chips from the table, so the director can seat them at another
+    /// table. Players cannot be removed while they are in a hand.
+    UnseatPlayer {
+        address: String,
+    },
+    /// Change the big blind from the next hand onwards. Only the tournament director may do this.
+    SetBigBlind {
+        big_blind: u32,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     ViewPreStartState {},
-    ViewGameState { permit: Permit },
+    ViewGameState {
+        permit: Permit,
+    },
+    /// View a page of everything that happened in a hand, starting from page 0.
+    HandHistory {
+        hand_id: u64,
+        permit: Permit,
+        page: Option<u32>,
+        page_size: Option<u32>,
+    },
+    /// View the hands revealed at the end of the last completed hand.
+    LastShowdown {
+        permit: Permit,
+    },
+    /// View the public state of the game, without anyone's hole cards. Anyone may watch, unless
+    /// the lobby disables spectators.
+    SpectateGame {},
+    /// View the commitment to a hand's shuffled deck, and once the hand is over, the commitment to
+    /// each card along with the board and shown hands.
+    VerifyHand {
+        hand_id: u64,
+    },
 }
@@ -0,0 +1,55 @@
+use cosmwasm_std::Storage;
+
+use crate::error::ContractError;
+use crate::msg::BettingStructure;
+use crate::poker::tournament::current_big_blind;
+use crate::state::{LOBBY_CONFIG, POT, REVEALED_CARDS, STREET_RAISES};
+
+/// The size of every bet and raise in a fixed limit game: the big blind before the flop and on
+/// the flop, and twice the big blind on the turn and river.
+pub fn fixed_limit_bet_size(storage: &dyn Storage) -> Result<u128, ContractError> {
+    let big_blind = current_big_blind(storage)? as u128;
+    if REVEALED_CARDS.load(storage)? >= 4 {
+        Ok(big_blind * 2)
+    } else {
+        Ok(big_blind)
+    }
+}
+
+/// Check a bet or raise to `to` against the lobby's betting structure. A player going all in may
+/// always bet less than the structure's bet size, but never more than its limit.
+pub fn check_bet_size(
+    to: u128,
+    min_bet: u128,
+    previous_bet_amount: u128,
+    is_all_in: bool,
+    storage: &dyn Storage,
+) -> Result<(), ContractError> {
+    match LOBBY_CONFIG.load(storage)?.betting_structure {
+        BettingStructure::NoLimit => Ok(()),
+        BettingStructure::PotLimit => {
+            // The largest raise is the size of the pot after calling the current bet.
+            let to_call = min_bet - previous_bet_amount;
+            let max_raise_to = min_bet + POT.load(storage)? + to_call;
+            if to > max_raise_to {
+                return Err(ContractError::BetAboveMaximum { max_raise_to });
+            }
+
+            Ok(())
+        }
+        BettingStructure::FixedLimit { raise_cap } => {
+            // The big blind counts as the first bet before the flop, so any bet over it is a raise.
+            let is_raise = min_bet > 0;
+            if is_raise && STREET_RAISES.load(storage)? >= raise_cap {
+                return Err(ContractError::RaiseCapReached);
+            }
+
+            let raise_to = min_bet + fixed_limit_bet_size(storage)?;
+            if to > raise_to || (to < raise_to && !is_all_in) {
+                return Err(ContractError::FixedBetSize { raise_to });
+            }
+
+            Ok(())
+        }
+    }
+}
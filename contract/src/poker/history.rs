@@ -0,0 +1,20 @@
+use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
+use secret_toolkit::storage::AppendStore;
+
+use crate::state::{HandEvent, HAND_HISTORY, HAND_ID, USERNAMES};
+
+/// The history of the hand with the given ID.
+pub fn hand_history(hand_id: u64) -> AppendStore<'static, HandEvent> {
+    HAND_HISTORY.add_suffix(&hand_id.to_be_bytes())
+}
+
+/// Append an event to the history of the current hand.
+pub fn record_event(event: &HandEvent, storage: &mut dyn Storage) -> StdResult<()> {
+    let hand_id = HAND_ID.load(storage)?;
+    hand_history(hand_id).push(storage, event)
+}
+
+/// The username a player is recorded under in the hand history.
+pub fn username(address: &CanonicalAddr, storage: &dyn Storage) -> String {
+    USERNAMES.get(storage, address).unwrap_or_default()
+}
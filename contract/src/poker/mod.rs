@@ -1,34 +1,22 @@
+pub mod betting;
+pub mod history;
+pub mod tournament;
+
 use cosmwasm_std::{CanonicalAddr, Env, StdError, StdResult, Storage};
 
-use poker_eval::{box_cards, Card, EvalClass, Rank, Suit};
+use poker_eval::{Card, Eval, EvalClass, Rank, Suit};
 
+use crate::error::ContractError;
+use crate::msg::{Ante, GameVariant};
 use crate::state::{
-    Deck, ALL_PLAYERS, BALANCES, BETS, BUTTON_POSITION, CURRENT_MIN_BET, CURRENT_TURN_POSITION,
-    HANDS, LOBBY_CONFIG, POT, REVEALED_CARDS, TABLE,
+    Deck, HandEvent, LastShowdown, ShownHand, Street, ALL_PLAYERS, BALANCES, BETS,
+    BIG_BLIND_POSITION, BUTTON_POSITION, CONTRIBUTIONS, CURRENT_MIN_BET, CURRENT_TURN_POSITION,
+    DEAD_MONEY, DECK_PROOFS, DIRECTOR, HANDS, HAND_ID, HAS_ACTED, IS_STARTED, LAST_RAISE_SIZE,
+    LAST_SHOWDOWN, LOBBY_CONFIG, PENDING_BIG_BLIND, PENDING_BUY_INS, POT, REVEALED_CARDS,
+    SHOWS_UNCONTESTED, SITTING_OUT, SMALL_BLIND_POSITION, STRADDLERS, STREET_RAISES, TABLE,
+    TURN_STARTED_AT,
 };
 
-pub fn find_next_player<'a>(
-    storage: &mut dyn Storage,
-    player_position: u8,
-    addresses: &'a [CanonicalAddr],
-) -> StdResult<(u8, &'a CanonicalAddr, u128)> {
-    addresses
-        .iter()
-        .cycle()
-        .enumerate()
-        .skip(player_position as usize)
-        .take(addresses.len())
-        .find_map(|(index, address)| {
-            if !HANDS.contains(storage, address) {
-                return None;
-            }
-            let balance = BALANCES.get(storage, address)?;
-            let position = (index % addresses.len()) as u8;
-            Some((position, address, balance))
-        })
-        .ok_or_else(|| StdError::generic_err("find_next_player found no elligible players"))
-}
-
 pub fn take_bet(
     bet_amount: u128,
     players_balance: u128,
@@ -45,84 +33,292 @@ pub fn take_bet(
     }
 
     BETS.insert(storage, players_address, &total_bet)?;
+    let contribution = CONTRIBUTIONS.get(storage, players_address).unwrap_or(0);
+    CONTRIBUTIONS.insert(storage, players_address, &(contribution + bet_amount))?;
     POT.update(storage, |pot| Ok(pot + bet_amount))?;
 
     Ok(())
 }
 
+/// Take a blind from the player, or as much of it as they have. Returns the amount taken.
 fn take_forced_bet(
-    take_amount: u32,
-    player_position: u8,
-    addresses: &[CanonicalAddr],
+    take_amount: u128,
+    player_address: &CanonicalAddr,
     storage: &mut dyn Storage,
-) -> StdResult<u8> {
-    let (index, player_address, players_balance) =
-        find_next_player(storage, player_position, addresses)?;
-
-    let bet_amount = if players_balance > take_amount as u128 {
-        take_amount as u128
-    } else {
-        players_balance
+) -> StdResult<u128> {
+    let Some(players_balance) = BALANCES.get(storage, player_address) else {
+        return Ok(0);
     };
 
+    let bet_amount = players_balance.min(take_amount);
+
     take_bet(
         bet_amount,
         players_balance,
         bet_amount,
-        &player_address,
+        player_address,
         storage,
     )?;
 
-    Ok(index)
+    Ok(bet_amount)
 }
 
-pub fn new_round(button_position: u8, storage: &mut dyn Storage, env: &Env) -> StdResult<()> {
-    let mut addresses: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
-    addresses.retain(|address| BALANCES.get(storage, address).is_some());
+/// Take an ante from the player, or as much of it as they have. An ante goes into the pot without
+/// counting towards the player's bet for the street. Returns the amount taken.
+fn take_ante(
+    take_amount: u32,
+    player_address: &CanonicalAddr,
+    storage: &mut dyn Storage,
+) -> StdResult<u128> {
+    let Some(players_balance) = BALANCES.get(storage, player_address) else {
+        return Ok(0);
+    };
 
-    let mut deck = Deck::new();
-    let Some(ref random) = env.block.random else {
-        return Err(StdError::generic_err("Random source was unavailable"));
+    let ante = players_balance.min(take_amount as u128);
+    if ante < players_balance {
+        BALANCES.insert(storage, player_address, &(players_balance - ante))?;
+    } else {
+        BALANCES.remove(storage, player_address)?;
+    }
+    POT.update(storage, |pot| Ok(pot + ante))?;
+
+    Ok(ante)
+}
+
+/// Find the next seat after `seat_position` belonging to a player who is dealt into the hand.
+fn next_active_seat(seat_position: u8, active_seats: &[bool]) -> StdResult<u8> {
+    (1..=active_seats.len())
+        .map(|offset| (seat_position as usize + offset) % active_seats.len())
+        .find(|seat| active_seats[*seat])
+        .map(|seat| seat as u8)
+        .ok_or_else(|| StdError::generic_err("next_active_seat found no active seats"))
+}
+
+/// Work out the button, small blind, and big blind seats for the next hand, returned in that
+/// order. The big blind always moves to the next active seat, and the small blind and button
+/// follow it into the seats the big blind and small blind previously occupied. Either of those
+/// seats may have been vacated, leaving a dead small blind or a dead button. When only two
+/// players are dealt in, the button posts the small blind instead.
+fn assign_blinds(active_seats: &[bool], storage: &dyn Storage) -> StdResult<(u8, u8, u8)> {
+    let is_heads_up = active_seats.iter().filter(|is_active| **is_active).count() == 2;
+
+    let Some(previous_big_blind) = BIG_BLIND_POSITION.may_load(storage)? else {
+        // In the first hand, the button goes to the first active seat.
+        let button = next_active_seat(active_seats.len() as u8 - 1, active_seats)?;
+        let small_blind = if is_heads_up {
+            button
+        } else {
+            next_active_seat(button, active_seats)?
+        };
+        let big_blind = next_active_seat(small_blind, active_seats)?;
+        return Ok((button, small_blind, big_blind));
     };
 
-    addresses.iter().try_for_each(|address| {
-        HANDS.insert(
-            storage,
-            address,
-            &(deck.draw(&random.0)?, deck.draw(&random.0)?),
-        )
+    let big_blind = next_active_seat(previous_big_blind, active_seats)?;
+
+    if is_heads_up {
+        let button = next_active_seat(big_blind, active_seats)?;
+        return Ok((button, button, big_blind));
+    }
+
+    let button = SMALL_BLIND_POSITION.load(storage)?;
+    Ok((button, previous_big_blind, big_blind))
+}
+
+/// Whether the player is dealt into new hands, having chips and not sitting out.
+fn is_active(address: &CanonicalAddr, storage: &dyn Storage) -> bool {
+    BALANCES.contains(storage, address) && !SITTING_OUT.contains(storage, address)
+}
+
+/// The number of players who will be dealt into the next hand.
+pub fn count_active_players(storage: &dyn Storage) -> StdResult<usize> {
+    Ok(ALL_PLAYERS
+        .iter(storage)?
+        .flatten()
+        .filter(|address| is_active(address, storage))
+        .count())
+}
+
+pub fn new_round(storage: &mut dyn Storage, env: &Env) -> Result<(), ContractError> {
+    let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
+
+    // Buy ins made during the previous hand take effect now.
+    players.iter().try_for_each(|address| {
+        let Some(pending_amount) = PENDING_BUY_INS.get(storage, address) else {
+            return Ok(());
+        };
+        let balance = BALANCES.get(storage, address).unwrap_or(0);
+        BALANCES.insert(storage, address, &(balance + pending_amount))?;
+        PENDING_BUY_INS.remove(storage, address)
     })?;
 
-    (0..5).try_for_each(|_| TABLE.push(storage, &deck.draw(random)?))?;
+    if let Some(big_blind) = PENDING_BIG_BLIND.may_load(storage)? {
+        LOBBY_CONFIG.update(storage, |mut lobby_config| {
+            lobby_config.big_blind = big_blind;
+            Ok(lobby_config)
+        })?;
+        PENDING_BIG_BLIND.remove(storage);
+    }
+
+    // The game was paused at the end of the hand, for the director to move players.
+    if !IS_STARTED.load(storage)? {
+        return Ok(());
+    }
+
+    let lobby_config = LOBBY_CONFIG.load(storage)?;
+    if let Some(ref tournament) = lobby_config.tournament {
+        if tournament::finish_tournament(tournament, &players, storage)? {
+            return Ok(IS_STARTED.save(storage, &false)?);
+        }
+    }
 
-    let big_blind_amount = LOBBY_CONFIG.load(storage)?.big_blind;
+    let active_seats: Vec<bool> = players
+        .iter()
+        .map(|address| is_active(address, storage))
+        .collect();
+
+    // The game is paused until enough players with chips are sitting in to play another hand.
+    if active_seats.iter().filter(|is_active| **is_active).count() < 2 {
+        return Ok(IS_STARTED.save(storage, &false)?);
+    }
+
+    let Some(ref random) = env.block.random else {
+        return Err(ContractError::RandomnessUnavailable);
+    };
+
+    // The hand ID is mixed into the shuffle, so hands dealt in the same block use different decks.
+    let hand_id = HAND_ID.may_load(storage)?.unwrap_or(0) + 1;
+    HAND_ID.save(storage, &hand_id)?;
+    if let Some(ref tournament) = lobby_config.tournament {
+        tournament::update_blind_level(tournament, hand_id, env, storage)?;
+    }
+    let mut deck = Deck::shuffled(
+        lobby_config.variant,
+        random.as_slice(),
+        &hand_id.to_be_bytes(),
+    );
+    DECK_PROOFS.insert(storage, &hand_id, &deck.proof())?;
+
+    players
+        .iter()
+        .zip(&active_seats)
+        .filter(|(_, is_active)| **is_active)
+        .try_for_each(|(address, _)| {
+            let hand = (0..lobby_config.variant.num_hole_cards())
+                .map(|_| deck.draw())
+                .collect::<StdResult<Vec<u8>>>()?;
+            HANDS.insert(storage, address, &hand)
+        })?;
+
+    (0..5).try_for_each(|_| TABLE.push(storage, &deck.draw()?))?;
+
+    let big_blind_amount = tournament::current_big_blind(storage)?;
     CURRENT_MIN_BET.save(storage, &(big_blind_amount as u128))?;
-    BUTTON_POSITION.save(storage, &button_position)?;
+    LAST_RAISE_SIZE.save(storage, &(big_blind_amount as u128))?;
+    STREET_RAISES.save(storage, &0)?;
 
-    // Small blind is immediately to the left of the button.
-    let small_blind_player_position = take_forced_bet(
-        big_blind_amount / 2,
-        button_position + 1,
-        &addresses,
-        storage,
-    )?;
+    let (button, small_blind, big_blind) = assign_blinds(&active_seats, storage)?;
+    BUTTON_POSITION.save(storage, &button)?;
+    SMALL_BLIND_POSITION.save(storage, &small_blind)?;
+    BIG_BLIND_POSITION.save(storage, &big_blind)?;
 
-    // Big blind is immediately to the left of the small blind
-    let big_blind_player_position = take_forced_bet(
-        big_blind_amount,
-        small_blind_player_position + 1,
-        &addresses,
+    let dealt_in_players = players
+        .iter()
+        .zip(&active_seats)
+        .enumerate()
+        .filter(|(_, (_, is_active))| **is_active)
+        .map(|(seat, (address, _))| {
+            let stack = BALANCES.get(storage, address).unwrap_or(0);
+            (seat as u8, history::username(address, storage), stack)
+        })
+        .collect();
+    history::record_event(
+        &HandEvent::Start {
+            time: env.block.time.seconds(),
+            button_seat: button,
+            big_blind: big_blind_amount,
+            variant: lobby_config.variant,
+            betting_structure: lobby_config.betting_structure.clone(),
+            players: dealt_in_players,
+        },
         storage,
     )?;
 
-    let next_player_position =
-        find_next_player(storage, big_blind_player_position + 1, &addresses)?.0;
-    CURRENT_TURN_POSITION.save(storage, &next_player_position)?;
+    // Antes are posted before the blinds, and count towards each player's share of the pot.
+    if let Some(Ante::EveryPlayer(ante)) = lobby_config.ante {
+        players
+            .iter()
+            .zip(&active_seats)
+            .filter(|(_, is_active)| **is_active)
+            .try_for_each(|(address, _)| {
+                let amount = take_ante(ante, address, storage)?;
+                CONTRIBUTIONS.insert(storage, address, &amount)?;
+                if amount > 0 {
+                    let player = history::username(address, storage);
+                    history::record_event(&HandEvent::Ante { player, amount }, storage)?;
+                }
+                Ok::<_, StdError>(())
+            })?;
+    }
 
-    Ok(())
+    // A dead small blind, left by a player who has gone or is sitting out, is not posted by
+    // anyone.
+    let small_blind_address = &players[small_blind as usize];
+    if active_seats[small_blind as usize] {
+        let amount = take_forced_bet(big_blind_amount as u128 / 2, small_blind_address, storage)?;
+        if amount > 0 {
+            let player = history::username(small_blind_address, storage);
+            history::record_event(&HandEvent::SmallBlind { player, amount }, storage)?;
+        }
+    }
+
+    let big_blind_address = &players[big_blind as usize];
+    let amount = take_forced_bet(big_blind_amount as u128, big_blind_address, storage)?;
+    if amount > 0 {
+        let player = history::username(big_blind_address, storage);
+        history::record_event(&HandEvent::BigBlind { player, amount }, storage)?;
+    }
+
+    // The big blind ante is posted after the big blind itself, so a short stacked big blind pays
+    // the blind first. It is dead money, rather than part of the big blind's contribution.
+    if let Some(Ante::BigBlind) = lobby_config.ante {
+        let amount = take_ante(big_blind_amount, big_blind_address, storage)?;
+        DEAD_MONEY.save(storage, &amount)?;
+        if amount > 0 {
+            let player = history::username(big_blind_address, storage);
+            history::record_event(&HandEvent::Ante { player, amount }, storage)?;
+        }
+    }
+
+    // Before the flop the action starts left of the big blind, which is the button when only
+    // two players are dealt in. A straddle moves the start of the action one seat further on.
+    let is_heads_up = active_seats.iter().filter(|is_active| **is_active).count() == 2;
+    if lobby_config.allow_straddle && !is_heads_up {
+        let under_the_gun = next_active_seat(big_blind, &active_seats)?;
+        let under_the_gun_address = &players[under_the_gun as usize];
+
+        if STRADDLERS.contains(storage, under_the_gun_address) {
+            let amount =
+                take_forced_bet(big_blind_amount as u128 * 2, under_the_gun_address, storage)?;
+            if amount > 0 {
+                let player = history::username(under_the_gun_address, storage);
+                history::record_event(&HandEvent::Straddle { player, amount }, storage)?;
+            }
+
+            // The straddle acts as a third blind, so the next raise must be at least its size.
+            let straddle = amount.max(big_blind_amount as u128);
+            CURRENT_MIN_BET.save(storage, &straddle)?;
+            LAST_RAISE_SIZE.save(storage, &straddle)?;
+            STREET_RAISES.save(storage, &1)?;
+
+            return end_turn(under_the_gun, storage, env);
+        }
+    }
+
+    end_turn(big_blind, storage, env)
 }
 
-fn u8_to_card(card: u8) -> Card {
+pub fn u8_to_card(card: u8) -> Card {
     let rank = match card % 13 {
         0 => Rank::Ace,
         1 => Rank::Two,
@@ -150,22 +346,268 @@ fn u8_to_card(card: u8) -> Card {
     Card::new(rank, suit)
 }
 
-fn u8s_to_cards(hand: (u8, u8)) -> (Card, Card) {
-    (u8_to_card(hand.0), u8_to_card(hand.1))
+/// Every way of choosing `size` of the cards, keeping their order.
+fn combinations(cards: &[Card], size: usize) -> Vec<Vec<Card>> {
+    if size == 0 {
+        return vec![vec![]];
+    }
+
+    (0..cards.len())
+        .flat_map(|index| {
+            combinations(&cards[index + 1..], size - 1)
+                .into_iter()
+                .map(move |mut combination| {
+                    combination.insert(0, cards[index]);
+                    combination
+                })
+        })
+        .collect()
 }
 
-fn distribute_pot(winners: &[&CanonicalAddr], storage: &mut dyn Storage) -> StdResult<()> {
-    let pot_value = POT.load(storage)?;
-    let individual_winnings = pot_value / winners.len() as u128;
-    winners.iter().try_for_each(|address| {
-        let balance = BALANCES.get(storage, address).unwrap_or(0);
-        BALANCES.insert(storage, address, &(balance + individual_winnings))
-    })?;
+/// How strong a hand is at showdown. Hands are compared by category first, which puts a flush
+/// above a full house in short deck, then by their evaluation within that category.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandStrength {
+    category: u8,
+    eval: Eval,
+    is_short_deck_wheel: bool,
+}
+
+impl HandStrength {
+    fn new(
+        evaluator: &poker_eval::Evaluator,
+        variant: GameVariant,
+        cards: &[Card],
+    ) -> Option<Self> {
+        // A-6-7-8-9 is the lowest straight in short deck, so it is evaluated as A-2-3-4-5.
+        let is_short_deck_wheel = variant == GameVariant::ShortDeck && is_short_deck_wheel(cards);
+        let eval = if is_short_deck_wheel {
+            let cards: Vec<Card> = cards
+                .iter()
+                .map(|card| {
+                    let rank = match card.rank() {
+                        Rank::Six => Rank::Two,
+                        Rank::Seven => Rank::Three,
+                        Rank::Eight => Rank::Four,
+                        Rank::Nine => Rank::Five,
+                        rank => rank,
+                    };
+                    Card::new(rank, card.suit())
+                })
+                .collect();
+            evaluator.evaluate(cards).ok()?
+        } else {
+            evaluator.evaluate(cards).ok()?
+        };
+
+        let category = match eval.class() {
+            EvalClass::HighCard { .. } => 0,
+            EvalClass::Pair { .. } => 1,
+            EvalClass::TwoPair { .. } => 2,
+            EvalClass::ThreeOfAKind { .. } => 3,
+            EvalClass::Straight { .. } => 4,
+            EvalClass::Flush { .. } if variant == GameVariant::ShortDeck => 6,
+            EvalClass::Flush { .. } => 5,
+            EvalClass::FullHouse { .. } if variant == GameVariant::ShortDeck => 5,
+            EvalClass::FullHouse { .. } => 6,
+            EvalClass::FourOfAKind { .. } => 7,
+            EvalClass::StraightFlush { .. } => 8,
+        };
+
+        Some(Self {
+            category,
+            eval,
+            is_short_deck_wheel,
+        })
+    }
+
+    /// The evaluation of the best five cards. A short deck A-6-7-8-9 straight is evaluated as
+    /// A-2-3-4-5.
+    pub fn eval(&self) -> Eval {
+        self.eval
+    }
 
-    let remaining_chips = pot_value % winners.len() as u128;
-    if remaining_chips > 0 {
-        let balance = BALANCES.get(storage, &winners[0]).unwrap();
-        BALANCES.insert(storage, &winners[0], &(balance + remaining_chips))?;
+    /// The name of the hand, such as "Flush, ace-high".
+    pub fn name(&self) -> String {
+        match self.eval.class() {
+            EvalClass::Straight { .. } if self.is_short_deck_wheel => {
+                "Straight, nine-high".to_string()
+            }
+            EvalClass::StraightFlush { .. } if self.is_short_deck_wheel => {
+                "Straight flush, nine-high".to_string()
+            }
+            _ => self.eval.to_string(),
+        }
+    }
+}
+
+fn is_short_deck_wheel(cards: &[Card]) -> bool {
+    let mut ranks: Vec<Rank> = cards.iter().map(|card| card.rank()).collect();
+    ranks.sort_unstable();
+    ranks == [Rank::Six, Rank::Seven, Rank::Eight, Rank::Nine, Rank::Ace]
+}
+
+/// Evaluate the best hand the player can make with the board. In Omaha, the hand must use
+/// exactly two hole cards and three board cards.
+pub fn evaluate_hand(
+    evaluator: &poker_eval::Evaluator,
+    variant: GameVariant,
+    hand: &[Card],
+    table: &[Card],
+) -> Option<HandStrength> {
+    let five_card_hands = match variant {
+        // The evaluator finds the best five of the cards itself, using the standard hand rankings.
+        GameVariant::Holdem => {
+            return HandStrength::new(evaluator, variant, &[hand, table].concat());
+        }
+        // Short deck ranks hands differently, so each five cards are evaluated separately.
+        GameVariant::ShortDeck => combinations(&[hand, table].concat(), 5),
+        GameVariant::Omaha => {
+            let board_combinations = combinations(table, 3);
+            combinations(hand, 2)
+                .into_iter()
+                .flat_map(|hole_cards| {
+                    board_combinations
+                        .iter()
+                        .map(move |board_cards| [hole_cards.as_slice(), board_cards].concat())
+                })
+                .collect()
+        }
+    };
+
+    five_card_hands
+        .iter()
+        .filter_map(|cards| HandStrength::new(evaluator, variant, cards))
+        .max()
+}
+
+/// A portion of the pot, along with the players who are eligible to win it.
+struct Pot<'a> {
+    value: u128,
+    eligible_players: Vec<&'a CanonicalAddr>,
+}
+
+/// Split the pot into a main pot and side pots using each player's total contribution to the
+/// hand. A player is only eligible to win a pot if they have not folded, and they have
+/// contributed at least as much as every other player eligible for that pot.
+fn build_pots<'a>(players: &'a [CanonicalAddr], storage: &dyn Storage) -> StdResult<Vec<Pot<'a>>> {
+    let contributions: Vec<(&CanonicalAddr, u128, bool)> = players
+        .iter()
+        .map(|address| {
+            let contribution = CONTRIBUTIONS.get(storage, address).unwrap_or(0);
+            (address, contribution, HANDS.contains(storage, address))
+        })
+        .collect();
+
+    let mut levels: Vec<u128> = contributions
+        .iter()
+        .filter(|(_, contribution, is_live)| *is_live && *contribution > 0)
+        .map(|(_, contribution, _)| *contribution)
+        .collect();
+    levels.sort_unstable();
+    levels.dedup();
+
+    let mut previous_level = 0;
+    let mut pots: Vec<Pot> = levels
+        .into_iter()
+        .map(|level| {
+            let value = contributions
+                .iter()
+                .map(|(_, contribution, _)| {
+                    (*contribution).min(level) - (*contribution).min(previous_level)
+                })
+                .sum();
+            let eligible_players = contributions
+                .iter()
+                .filter(|(_, contribution, is_live)| *is_live && *contribution >= level)
+                .map(|(address, _, _)| *address)
+                .collect();
+            previous_level = level;
+
+            Pot {
+                value,
+                eligible_players,
+            }
+        })
+        .collect();
+
+    // Dead money belongs to the main pot, and chips folded above the largest live contribution
+    // belong to the last pot.
+    let dead_money = DEAD_MONEY.may_load(storage)?.unwrap_or(0);
+    let assigned_chips: u128 = pots.iter().map(|pot| pot.value).sum();
+    let unassigned_chips = POT.load(storage)?.saturating_sub(assigned_chips);
+    if let Some(pot) = pots.first_mut() {
+        pot.value += dead_money;
+    }
+    match pots.last_mut() {
+        Some(pot) => pot.value += unassigned_chips.saturating_sub(dead_money),
+        None => pots.push(Pot {
+            value: unassigned_chips,
+            eligible_players: contributions
+                .iter()
+                .filter(|(_, _, is_live)| *is_live)
+                .map(|(address, _, _)| *address)
+                .collect(),
+        }),
+    }
+
+    Ok(pots)
+}
+
+fn distribute_pot(
+    results: &[(&CanonicalAddr, HandStrength)],
+    players: &[CanonicalAddr],
+    storage: &mut dyn Storage,
+) -> StdResult<()> {
+    for (pot_index, pot) in build_pots(players, storage)?.into_iter().enumerate() {
+        let eligible_results: Vec<&(&CanonicalAddr, HandStrength)> = results
+            .iter()
+            .filter(|(address, _)| pot.eligible_players.contains(address))
+            .collect();
+
+        let Some(highest_result) = eligible_results.iter().map(|result| result.1).max() else {
+            continue;
+        };
+
+        // Collect all winners of this pot whose hands are exactly as strong as the best hand,
+        // kickers included
+        let winners: Vec<&CanonicalAddr> = eligible_results
+            .into_iter()
+            .filter_map(|(address, result)| {
+                if *result == highest_result {
+                    Some(*address)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // The first winner also takes any odd chips left over from splitting the pot.
+        let individual_winnings = pot.value / winners.len() as u128;
+        let remaining_chips = pot.value % winners.len() as u128;
+        winners
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, address)| {
+                let amount = if index == 0 {
+                    individual_winnings + remaining_chips
+                } else {
+                    individual_winnings
+                };
+
+                let balance = BALANCES.get(storage, address).unwrap_or(0);
+                BALANCES.insert(storage, address, &(balance + amount))?;
+
+                let player = history::username(address, storage);
+                history::record_event(
+                    &HandEvent::Win {
+                        player,
+                        amount,
+                        pot: pot_index as u8,
+                    },
+                    storage,
+                )
+            })?;
     }
 
     Ok(())
@@ -174,93 +616,348 @@ fn distribute_pot(winners: &[&CanonicalAddr], storage: &mut dyn Storage) -> StdR
 fn showdown(players: &[CanonicalAddr], storage: &mut dyn Storage) -> StdResult<()> {
     let table: Vec<Card> = TABLE.iter(storage)?.flatten().map(u8_to_card).collect();
     let evaluator = poker_eval::Evaluator::new();
+    let variant = LOBBY_CONFIG.load(storage)?.variant;
 
-    let results: Vec<(&CanonicalAddr, EvalClass)> = players
+    // Each player plays their best five card hand, and hands are compared including kickers.
+    let results: Vec<(&CanonicalAddr, HandStrength)> = players
         .iter()
         .filter_map(|address| {
-            let hand: [Card; 2] = u8s_to_cards(HANDS.get(storage, &address)?).into();
-            let cards = box_cards!(hand, table);
-            let result = evaluator.evaluate(cards).ok()?.class();
+            let hand: Vec<Card> = HANDS
+                .get(storage, address)?
+                .into_iter()
+                .map(u8_to_card)
+                .collect();
+            let result = evaluate_hand(&evaluator, variant, &hand, &table)?;
 
             Some((address, result))
         })
         .collect();
 
-    let highest_result = results.iter().map(|result| result.1).max().unwrap();
-
-    // Collect all winners with the highest evaluation class
-    let winners: Vec<&CanonicalAddr> = results
-        .into_iter()
-        .filter_map(|(address, result)| {
-            if result == highest_result {
-                Some(address)
-            } else {
-                None
-            }
-        })
-        .collect();
+    let mut shown_hands = Vec::new();
+    for address in players {
+        let Some(hand) = HANDS.get(storage, address) else {
+            continue;
+        };
+        let hand_name = results
+            .iter()
+            .find(|(result_address, _)| *result_address == address)
+            .map(|(_, result)| result.name());
+
+        let player = history::username(address, storage);
+        history::record_event(
+            &HandEvent::Show {
+                player: player.clone(),
+                hand: hand.clone(),
+            },
+            storage,
+        )?;
+        shown_hands.push(ShownHand {
+            player,
+            hand,
+            hand_name,
+        });
+    }
+    save_last_showdown(shown_hands, storage)?;
 
-    distribute_pot(&winners, storage)?;
+    distribute_pot(&results, players, storage)?;
 
     Ok(())
 }
 
+/// Keep the board and the revealed hands of the hand that just ended, until the next one ends.
+fn save_last_showdown(hands: Vec<ShownHand>, storage: &mut dyn Storage) -> StdResult<()> {
+    let table = TABLE
+        .iter(storage)?
+        .take(REVEALED_CARDS.load(storage)? as usize)
+        .collect::<StdResult<_>>()?;
+
+    let last_showdown = LastShowdown {
+        hand_id: HAND_ID.load(storage)?,
+        table,
+        hands,
+    };
+
+    LAST_SHOWDOWN.save(storage, &last_showdown)
+}
+
 fn end_round(players: &[CanonicalAddr], storage: &mut dyn Storage) -> StdResult<()> {
+    if LOBBY_CONFIG.load(storage)?.tournament.is_some() {
+        tournament::eliminate_busted_players(players, storage)?;
+    }
+
     players.iter().try_for_each(|address| {
-        HANDS.remove(storage, &address)?;
-        BETS.remove(storage, &address)
+        HANDS.remove(storage, address)?;
+        HAS_ACTED.remove(storage, address)?;
+        CONTRIBUTIONS.remove(storage, address)?;
+        BETS.remove(storage, address)
     })?;
 
     TABLE.clear(storage);
     REVEALED_CARDS.save(storage, &0)?;
     POT.save(storage, &0)?;
+    DEAD_MONEY.remove(storage);
+
+    // A table run by a tournament director waits between hands until the director has balanced
+    // the tables and deals the next hand.
+    if DIRECTOR.may_load(storage)?.is_some() {
+        IS_STARTED.save(storage, &false)?;
+    }
+
+    // Now that the hand is over, anyone may check the cards that were seen against the deck's
+    // commitment. Mucked hands and cards that were never dealt stay hidden.
+    let hand_id = HAND_ID.load(storage)?;
+    if let Some(mut proof) = DECK_PROOFS.get(storage, &hand_id) {
+        let seen_cards: Vec<u8> = LAST_SHOWDOWN
+            .may_load(storage)?
+            .filter(|last_showdown| last_showdown.hand_id == hand_id)
+            .map(|last_showdown| {
+                let shown_cards = last_showdown.hands.into_iter().flat_map(|shown| shown.hand);
+                last_showdown.table.into_iter().chain(shown_cards).collect()
+            })
+            .unwrap_or_default();
+
+        proof.seen_positions = seen_cards
+            .iter()
+            .filter_map(|card| proof.cards.iter().position(|dealt| dealt == card))
+            .map(|position| position as u8)
+            .collect();
+        proof.seen_positions.sort_unstable();
+        proof.is_revealed = true;
+        DECK_PROOFS.insert(storage, &hand_id, &proof)?;
+    }
+
     Ok(())
 }
 
-pub fn next_play(storage: &mut dyn Storage) -> StdResult<bool> {
-    let current_num_cards = REVEALED_CARDS.load(storage)?;
-    if current_num_cards == 0 {
-        REVEALED_CARDS.save(storage, &3)?;
-    } else if current_num_cards < 5 {
-        REVEALED_CARDS.update(storage, |num_cards| Ok(num_cards + 1))?;
-    } else {
-        let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
-        showdown(&players, storage)?;
-        end_round(&players, storage)?;
-        return Ok(true);
+/// If every other player has folded, award the whole pot to the last player holding cards, who
+/// only reveals their hand if they have chosen to show, and end the round. Returns whether the
+/// round was ended.
+pub fn award_uncontested_pot(storage: &mut dyn Storage) -> StdResult<bool> {
+    let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
+
+    let live_players: Vec<&CanonicalAddr> = players
+        .iter()
+        .filter(|address| HANDS.contains(storage, address))
+        .collect();
+    let [winner] = live_players[..] else {
+        return Ok(false);
+    };
+
+    let amount = POT.load(storage)?;
+    let winnings = BALANCES.get(storage, winner).unwrap_or(0) + amount;
+    BALANCES.insert(storage, winner, &winnings)?;
+
+    let player = history::username(winner, storage);
+    history::record_event(
+        &HandEvent::Win {
+            player,
+            amount,
+            pot: 0,
+        },
+        storage,
+    )?;
+
+    let mut shown_hands = Vec::new();
+    if SHOWS_UNCONTESTED.contains(storage, winner) {
+        let hand = HANDS.get(storage, winner).unwrap_or_default();
+        let table: Vec<Card> = TABLE
+            .iter(storage)?
+            .take(REVEALED_CARDS.load(storage)? as usize)
+            .flatten()
+            .map(u8_to_card)
+            .collect();
+        let hole_cards: Vec<Card> = hand.iter().copied().map(u8_to_card).collect();
+        let variant = LOBBY_CONFIG.load(storage)?.variant;
+        let hand_name = evaluate_hand(&poker_eval::Evaluator::new(), variant, &hole_cards, &table)
+            .map(|result| result.name());
+
+        let player = history::username(winner, storage);
+        history::record_event(
+            &HandEvent::Show {
+                player: player.clone(),
+                hand: hand.clone(),
+            },
+            storage,
+        )?;
+        shown_hands.push(ShownHand {
+            player,
+            hand,
+            hand_name,
+        });
     }
+    save_last_showdown(shown_hands, storage)?;
 
-    Ok(false)
+    end_round(&players, storage)?;
+
+    Ok(true)
+}
+
+/// Record that the player has acted since the last full raise.
+pub fn mark_acted(player_address: &CanonicalAddr, storage: &mut dyn Storage) -> StdResult<()> {
+    HAS_ACTED.insert(storage, player_address, &true)
 }
 
-pub fn find_next_player_lazy(
+/// Give every player a chance to act again, after a full bet or raise.
+pub fn reopen_action(storage: &mut dyn Storage) -> StdResult<()> {
+    let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
+    players
+        .iter()
+        .try_for_each(|address| HAS_ACTED.remove(storage, address))
+}
+
+/// Start a new betting round, with no bets placed and no minimum bet. Chips bet on earlier
+/// streets remain in `CONTRIBUTIONS`.
+fn reset_street(storage: &mut dyn Storage) -> StdResult<()> {
+    let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
+    players.iter().try_for_each(|address| {
+        HAS_ACTED.remove(storage, address)?;
+        BETS.remove(storage, address)
+    })?;
+
+    // The minimum bet or raise on each street is set back to the big blind.
+    let big_blind_amount = tournament::current_big_blind(storage)?;
+    LAST_RAISE_SIZE.save(storage, &(big_blind_amount as u128))?;
+    STREET_RAISES.save(storage, &0)?;
+    CURRENT_MIN_BET.save(storage, &0)
+}
+
+/// Whether the player may still put more chips in the pot this round.
+fn can_act(address: &CanonicalAddr, storage: &dyn Storage) -> bool {
+    HANDS.contains(storage, address) && BALANCES.contains(storage, address)
+}
+
+/// Find the position of the next player who still needs to act in the current betting round,
+/// starting from `player_position`. A player needs to act if they have not acted since the last
+/// full raise, or if they have not yet matched the current bet. Returns `None` once the betting
+/// round is complete.
+pub fn find_next_player_to_act(
     player_position: u8,
-    storage: &mut dyn Storage,
-) -> StdResult<(u8, CanonicalAddr)> {
-    let eligibility_filter = |(index, address)| {
-        if !HANDS.contains(storage, &address) || !BALANCES.contains(storage, &address) {
-            return None;
+    storage: &dyn Storage,
+) -> StdResult<Option<u8>> {
+    let min_bet = CURRENT_MIN_BET.load(storage)?;
+    let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
+
+    Ok(players
+        .iter()
+        .enumerate()
+        .cycle()
+        .skip(player_position as usize)
+        .take(players.len())
+        .find_map(|(index, address)| {
+            let needs_to_act = !HAS_ACTED.contains(storage, address)
+                || BETS.get(storage, address).unwrap_or(0) < min_bet;
+            if can_act(address, storage) && needs_to_act {
+                Some(index as u8)
+            } else {
+                None
+            }
+        }))
+}
+
+/// The number of players still holding cards who have chips left to bet with.
+pub fn count_players_able_to_act(storage: &dyn Storage) -> StdResult<usize> {
+    Ok(ALL_PLAYERS
+        .iter(storage)?
+        .flatten()
+        .filter(|address| can_act(address, storage))
+        .count())
+}
+
+pub fn next_play(storage: &mut dyn Storage) -> StdResult<bool> {
+    reset_street(storage)?;
+
+    let current_num_cards = REVEALED_CARDS.load(storage)?;
+    let (street, new_num_cards) = match current_num_cards {
+        0 => (Street::Flop, 3),
+        3 => (Street::Turn, 4),
+        4 => (Street::River, 5),
+        _ => {
+            let players: Vec<CanonicalAddr> = ALL_PLAYERS.iter(storage)?.flatten().collect();
+            showdown(&players, storage)?;
+            end_round(&players, storage)?;
+            return Ok(true);
         }
-        Some((index as u8, address))
     };
+    REVEALED_CARDS.save(storage, &new_num_cards)?;
 
-    if let Some(user) = ALL_PLAYERS
+    let cards = TABLE
         .iter(storage)?
-        .flatten()
-        .enumerate()
-        .skip(player_position as usize)
-        .find_map(eligibility_filter)
+        .skip(current_num_cards as usize)
+        .take((new_num_cards - current_num_cards) as usize)
+        .collect::<StdResult<_>>()?;
+    history::record_event(&HandEvent::Board { street, cards }, storage)?;
+
+    Ok(false)
+}
+
+/// Pass the action on from the player at `player_position` to the next player who needs to act,
+/// moving on to the next street once the betting round is complete. Once fewer than two players
+/// can still bet, the remaining cards are revealed without any more betting.
+pub fn end_turn(
+    player_position: u8,
+    storage: &mut dyn Storage,
+    env: &Env,
+) -> Result<(), ContractError> {
+    let num_players = ALL_PLAYERS.get_len(storage)? as u8;
+    let players_able_to_act = count_players_able_to_act(storage)?;
+
+    if let Some(next_player_position) =
+        find_next_player_to_act((player_position + 1) % num_players, storage)?
     {
-        Ok(user)
-    } else {
-        ALL_PLAYERS
-            .iter(storage)?
-            .flatten()
-            .enumerate()
-            .take(player_position as usize)
-            .find_map(eligibility_filter)
-            .ok_or_else(|| {
-                StdError::generic_err("find_next_player_lazy found no elligible players")
-            })
+        let next_players_address = ALL_PLAYERS.get_at(storage, next_player_position as u32)?;
+        let next_players_bet = BETS.get(storage, &next_players_address).unwrap_or(0);
+
+        // A player with nobody left to bet against only needs to act to call an all in.
+        if players_able_to_act >= 2 || next_players_bet < CURRENT_MIN_BET.load(storage)? {
+            return start_turn(next_player_position, storage, env);
+        }
     }
+
+    loop {
+        if next_play(storage)? {
+            return new_round(storage, env);
+        }
+
+        if count_players_able_to_act(storage)? >= 2 {
+            break;
+        }
+    }
+
+    // After the flop the action starts left of the button.
+    let button_position = BUTTON_POSITION.load(storage)?;
+    let next_player_position =
+        find_next_player_to_act((button_position + 1) % num_players, storage)?
+            .ok_or_else(|| StdError::generic_err("end_turn found no players able to act"))?;
+    start_turn(next_player_position, storage, env)
+}
+
+/// Give the action to the player at `player_position`, starting the clock on their turn.
+fn start_turn(
+    player_position: u8,
+    storage: &mut dyn Storage,
+    env: &Env,
+) -> Result<(), ContractError> {
+    TURN_STARTED_AT.save(storage, &env.block.time.seconds())?;
+    CURRENT_TURN_POSITION.save(storage, &player_position)?;
+    Ok(())
+}
+
+/// Fold the hand of the player at `player_position`, awarding the pot straight away if only one
+/// player is left holding cards.
+pub fn fold_hand(
+    player_address: &CanonicalAddr,
+    player_position: u8,
+    storage: &mut dyn Storage,
+    env: &Env,
+) -> Result<(), ContractError> {
+    HANDS.remove(storage, player_address)?;
+
+    let player = history::username(player_address, storage);
+    history::record_event(&HandEvent::Fold { player }, storage)?;
+
+    if award_uncontested_pot(storage)? {
+        return new_round(storage, env);
+    }
+
+    end_turn(player_position, storage, env)
 }
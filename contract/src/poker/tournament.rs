@@ -0,0 +1,161 @@
+use cosmwasm_std::{CanonicalAddr, Env, StdResult, Storage};
+use secret_toolkit::storage::{AppendStore, Item};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::ContractError;
+use crate::msg::{BlindLevelDuration, TournamentConfig};
+use crate::state::{
+    BALANCES, BLIND_LEVEL, CONTRIBUTIONS, ELIMINATED, LOBBY_CONFIG, MAX_SEATS, PRIZES, PRIZE_POOL,
+    TOURNAMENT_STARTED_AT, TOURNAMENT_WINNER,
+};
+
+/// Check that the tournament can be played, and pays out the whole prize pool.
+pub fn validate_config(tournament: &TournamentConfig) -> Result<(), ContractError> {
+    let has_valid_duration = match tournament.level_duration {
+        BlindLevelDuration::Hands(hands) => hands > 0,
+        BlindLevelDuration::Seconds(seconds) => seconds > 0,
+    };
+    let Some(&first_big_blind) = tournament.blind_levels.first() else {
+        return Err(ContractError::InvalidBlindSchedule);
+    };
+    if !has_valid_duration || tournament.blind_levels.contains(&0) {
+        return Err(ContractError::InvalidBlindSchedule);
+    }
+
+    if tournament.starting_stack < first_big_blind {
+        return Err(ContractError::InvalidStartingStack);
+    }
+
+    let total_percentage: u32 = tournament.payouts.iter().map(|&payout| payout as u32).sum();
+    if tournament.payouts.is_empty()
+        || tournament.payouts.len() > MAX_SEATS as usize
+        || total_percentage != 100
+    {
+        return Err(ContractError::InvalidPayouts);
+    }
+
+    Ok(())
+}
+
+/// The big blind of the current hand, which rises with each blind level of a tournament.
+pub fn current_big_blind(storage: &dyn Storage) -> StdResult<u32> {
+    let lobby_config = LOBBY_CONFIG.load(storage)?;
+    let Some(tournament) = lobby_config.tournament else {
+        return Ok(lobby_config.big_blind);
+    };
+
+    let blind_level = BLIND_LEVEL.may_load(storage)?.unwrap_or(0);
+    Ok(tournament.blind_levels[blind_level as usize])
+}
+
+/// Move up to the blind level the schedule has reached by the time the hand `hand_id` is dealt.
+/// Levels only change between hands, so the blinds stay the same for the whole of a hand.
+pub fn update_blind_level(
+    tournament: &TournamentConfig,
+    hand_id: u64,
+    env: &Env,
+    storage: &mut dyn Storage,
+) -> StdResult<()> {
+    let levels_passed = match tournament.level_duration {
+        BlindLevelDuration::Hands(hands) => (hand_id - 1) / hands as u64,
+        BlindLevelDuration::Seconds(seconds) => {
+            let started_at = TOURNAMENT_STARTED_AT.load(storage)?;
+            env.block.time.seconds().saturating_sub(started_at) / seconds
+        }
+    };
+
+    let last_level = tournament.blind_levels.len() as u64 - 1;
+    BLIND_LEVEL.save(storage, &(levels_passed.min(last_level) as u32))
+}
+
+/// Eliminate the players who lost the last of their chips in the hand that has just ended.
+/// Players knocked out in the same hand are eliminated in order of the chips they started the
+/// hand with, so the player who had the larger stack finishes higher.
+pub fn eliminate_busted_players(
+    players: &[CanonicalAddr],
+    storage: &mut dyn Storage,
+) -> StdResult<()> {
+    // A player who busted has contributed their whole starting stack to the pot.
+    let mut busted_players: Vec<(&CanonicalAddr, u128)> = players
+        .iter()
+        .filter(|address| !BALANCES.contains(storage, address))
+        .filter_map(|address| Some((address, CONTRIBUTIONS.get(storage, address)?)))
+        .collect();
+    busted_players.sort_by_key(|(_, starting_stack)| *starting_stack);
+
+    busted_players
+        .into_iter()
+        .try_for_each(|(address, _)| ELIMINATED.push(storage, address))
+}
+
+/// Every player who has finished the tournament, starting with the winner. Takes the stores
+/// holding the winner and the eliminated players, as the tournament director keeps its own.
+pub fn finishing_order<T: Serialize + DeserializeOwned>(
+    winner: &Item<T>,
+    eliminated: &AppendStore<T>,
+    storage: &dyn Storage,
+) -> StdResult<Vec<T>> {
+    let Some(winner) = winner.may_load(storage)? else {
+        return Ok(vec![]);
+    };
+
+    let mut finishing_order: Vec<T> = eliminated.iter(storage)?.flatten().collect();
+    finishing_order.push(winner);
+    finishing_order.reverse();
+
+    Ok(finishing_order)
+}
+
+/// The uSCRT won by each finishing position of the payout table, starting with the winner. Empty
+/// until the tournament is over.
+pub fn prizes(
+    tournament: &TournamentConfig,
+    storage: &dyn Storage,
+) -> StdResult<Vec<(CanonicalAddr, u128)>> {
+    let finishing_order = finishing_order(&TOURNAMENT_WINNER, &ELIMINATED, storage)?;
+    if finishing_order.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let prizes = share_prize_pool(PRIZE_POOL.load(storage)?, &tournament.payouts);
+    Ok(finishing_order.into_iter().zip(prizes).collect())
+}
+
+/// Split the prize pool between the finishing positions of the payout table, starting with
+/// first place. Any uSCRT left over from rounding the prizes down goes to the winner.
+pub fn share_prize_pool(prize_pool: u128, payouts: &[u8]) -> Vec<u128> {
+    let mut prizes: Vec<u128> = payouts
+        .iter()
+        .map(|&percentage| prize_pool * percentage as u128 / 100)
+        .collect();
+
+    let total_prizes: u128 = prizes.iter().sum();
+    if let Some(prize) = prizes.first_mut() {
+        *prize += prize_pool - total_prizes;
+    }
+
+    prizes
+}
+
+/// End the tournament once only one player has chips left, sharing the prize pool between the
+/// finishing positions of the payout table. Returns whether the tournament is over.
+pub fn finish_tournament(
+    tournament: &TournamentConfig,
+    players: &[CanonicalAddr],
+    storage: &mut dyn Storage,
+) -> StdResult<bool> {
+    let remaining_players: Vec<&CanonicalAddr> = players
+        .iter()
+        .filter(|address| BALANCES.contains(storage, address))
+        .collect();
+    let [winner] = remaining_players[..] else {
+        return Ok(false);
+    };
+    TOURNAMENT_WINNER.save(storage, winner)?;
+
+    prizes(tournament, storage)?
+        .iter()
+        .try_for_each(|(address, prize)| PRIZES.insert(storage, address, prize))?;
+
+    Ok(true)
+}
@@ -0,0 +1,235 @@
+use crate::msg::{BettingStructure, GameVariant};
+use crate::state::{HandEvent, HandHistoryResponse, Street};
+
+const RANKS: [char; 13] = [
+    'A', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K',
+];
+const SUITS: [char; 4] = ['h', 'd', 'c', 's'];
+
+/// Write a card in the two character format used by hand histories, such as `Ah` or `Tc`.
+fn card_to_string(card: u8) -> String {
+    format!(
+        "{}{}",
+        RANKS[(card % 13) as usize],
+        SUITS[(card / 13) as usize]
+    )
+}
+
+fn cards_to_string(cards: &[u8]) -> String {
+    cards
+        .iter()
+        .map(|card| card_to_string(*card))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Format a Unix timestamp as a UTC date and time, such as `2024/01/31 18:05:00`.
+fn format_time(seconds: u64) -> String {
+    let days = (seconds / 86_400) as i64;
+    let seconds_of_day = seconds % 86_400;
+
+    // Convert days since the epoch to a civil date, using the proleptic Gregorian calendar.
+    let shifted_days = days + 719_468;
+    let era = shifted_days.div_euclid(146_097);
+    let day_of_era = shifted_days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}/{month:02}/{day:02} {:02}:{:02}:{:02}",
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+    )
+}
+
+/// Convert the recorded events of a hand into a PokerStars style plain text hand history, which
+/// can be imported into poker tracking software. Chip amounts are written in uSCRT.
+pub fn format_hand(hand_id: u64, events: &[HandEvent]) -> String {
+    let has_side_pots = events
+        .iter()
+        .any(|event| matches!(event, HandEvent::Win { pot, .. } if *pot > 0));
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut board: Vec<u8> = Vec::new();
+    let mut current_bet = 0;
+    let mut total_pot = 0;
+    let mut is_dealt = false;
+    let mut is_pot_awarded = false;
+
+    for event in events {
+        // The hole cards are dealt once every forced bet has been posted.
+        let is_forced_bet = matches!(
+            event,
+            HandEvent::Start { .. }
+                | HandEvent::Ante { .. }
+                | HandEvent::SmallBlind { .. }
+                | HandEvent::BigBlind { .. }
+                | HandEvent::Straddle { .. }
+        );
+        if !is_forced_bet && !is_dealt {
+            lines.push("*** HOLE CARDS ***".to_string());
+            is_dealt = true;
+        }
+
+        match event {
+            HandEvent::Start {
+                time,
+                button_seat,
+                big_blind,
+                variant,
+                betting_structure,
+                players,
+            } => {
+                current_bet = *big_blind as u128;
+                let game = match variant {
+                    GameVariant::Holdem => "Hold'em",
+                    GameVariant::Omaha => "Omaha",
+                    GameVariant::ShortDeck => "Hold'em Short Deck",
+                };
+                // Limit games are described by their small and big bets, rather than the blinds.
+                let (limit, stakes) = match betting_structure {
+                    BettingStructure::NoLimit => ("No Limit", (big_blind / 2, *big_blind)),
+                    BettingStructure::PotLimit => ("Pot Limit", (big_blind / 2, *big_blind)),
+                    BettingStructure::FixedLimit { .. } => ("Limit", (*big_blind, big_blind * 2)),
+                };
+                lines.push(format!(
+                    "PokerStars Hand #{hand_id}: {game} {limit} ({}/{}) - {} UTC",
+                    stakes.0,
+                    stakes.1,
+                    format_time(*time),
+                ));
+                lines.push(format!(
+                    "Table 'Chain Poker' 9-max Seat #{} is the button",
+                    button_seat + 1,
+                ));
+                lines.extend(players.iter().map(|(seat, player, stack)| {
+                    format!("Seat {}: {player} ({stack} in chips)", seat + 1)
+                }));
+            }
+            HandEvent::Ante { player, amount } => {
+                lines.push(format!("{player}: posts the ante {amount}"));
+            }
+            HandEvent::SmallBlind { player, amount } => {
+                lines.push(format!("{player}: posts small blind {amount}"));
+            }
+            HandEvent::BigBlind { player, amount } => {
+                lines.push(format!("{player}: posts big blind {amount}"));
+            }
+            HandEvent::Straddle { player, amount } => {
+                lines.push(format!("{player}: posts straddle {amount}"));
+                current_bet = current_bet.max(*amount);
+            }
+            HandEvent::Fold { player } => lines.push(format!("{player}: folds")),
+            HandEvent::Check { player } => lines.push(format!("{player}: checks")),
+            HandEvent::Call {
+                player,
+                amount,
+                is_all_in,
+            } => lines.push(format!("{player}: calls {amount}{}", all_in(*is_all_in))),
+            HandEvent::Raise {
+                player,
+                to,
+                is_all_in,
+            } => {
+                if current_bet == 0 {
+                    lines.push(format!("{player}: bets {to}{}", all_in(*is_all_in)));
+                } else {
+                    lines.push(format!(
+                        "{player}: raises {} to {to}{}",
+                        to.saturating_sub(current_bet),
+                        all_in(*is_all_in),
+                    ));
+                }
+                current_bet = current_bet.max(*to);
+            }
+            HandEvent::Board { street, cards } => {
+                let name = match street {
+                    Street::Flop => "FLOP",
+                    Street::Turn => "TURN",
+                    Street::River => "RIVER",
+                };
+                if board.is_empty() {
+                    lines.push(format!("*** {name} *** [{}]", cards_to_string(cards)));
+                } else {
+                    lines.push(format!(
+                        "*** {name} *** [{}] [{}]",
+                        cards_to_string(&board),
+                        cards_to_string(cards),
+                    ));
+                }
+                board.extend(cards);
+                current_bet = 0;
+            }
+            HandEvent::Show { player, hand } => {
+                // A player who wins uncontested may show after collecting the pot, without a
+                // showdown.
+                if !is_pot_awarded && !lines.iter().any(|line| line == "*** SHOW DOWN ***") {
+                    lines.push("*** SHOW DOWN ***".to_string());
+                }
+                lines.push(format!("{player}: shows [{}]", cards_to_string(hand)));
+            }
+            HandEvent::Win {
+                player,
+                amount,
+                pot,
+            } => {
+                let pot_name = match (has_side_pots, pot) {
+                    (false, _) => "pot".to_string(),
+                    (true, 0) => "main pot".to_string(),
+                    (true, pot) => format!("side pot-{pot}"),
+                };
+                lines.push(format!("{player} collected {amount} from {pot_name}"));
+                total_pot += amount;
+                is_pot_awarded = true;
+            }
+        }
+    }
+
+    lines.push("*** SUMMARY ***".to_string());
+    lines.push(format!("Total pot {total_pot} | Rake 0"));
+    if !board.is_empty() {
+        lines.push(format!("Board [{}]", cards_to_string(&board)));
+    }
+
+    lines.join("\n")
+}
+
+fn all_in(is_all_in: bool) -> &'static str {
+    if is_all_in {
+        " and is all-in"
+    } else {
+        ""
+    }
+}
+
+/// Convert pages of hand history query responses into PokerStars style hand histories. Pages of
+/// the same hand are joined in the order given, and hands are separated by blank lines.
+pub fn format_hand_histories(pages: &[HandHistoryResponse]) -> String {
+    let mut hands: Vec<(u64, Vec<HandEvent>)> = Vec::new();
+
+    for page in pages {
+        match hands
+            .iter_mut()
+            .find(|(hand_id, _)| *hand_id == page.hand_id)
+        {
+            Some((_, events)) => events.extend(page.events.iter().cloned()),
+            None => hands.push((page.hand_id, page.events.clone())),
+        }
+    }
+
+    hands
+        .iter()
+        .map(|(hand_id, events)| format_hand(*hand_id, events))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
@@ -1,34 +1,123 @@
-use cosmwasm_std::{CanonicalAddr, Deps, StdError, StdResult};
+use cosmwasm_std::{Binary, CanonicalAddr, Deps, StdError, StdResult, Storage};
+use rand_core::RngCore;
 use secret_toolkit::{
+    crypto::{sha_256, ContractPrng},
     serialization::Bincode2,
     storage::{AppendStore, Item, Keymap, KeymapBuilder, WithoutIter},
 };
 use serde::{Deserialize, Serialize};
 
+use crate::msg::{Ante, BettingStructure, GameVariant, TournamentConfig};
+
+/// Most players that can be seated at a table.
+pub const MAX_SEATS: u32 = 9;
+/// Shortest username a player may choose.
+pub const MIN_USERNAME_LENGTH: usize = 3;
+/// Longest username a player may choose.
+pub const MAX_USERNAME_LENGTH: usize = 15;
+
 pub static LOBBY_CONFIG: Item<LobbyConfig> = Item::new(b"lobby_config");
 pub static ALL_PLAYERS: AppendStore<CanonicalAddr> = AppendStore::new(b"players");
 pub static USERNAMES: Keymap<CanonicalAddr, String, Bincode2, WithoutIter> =
     KeymapBuilder::new(b"usernames").without_iter().build();
-pub static HANDS: Keymap<CanonicalAddr, (u8, u8), Bincode2, WithoutIter> =
+/// The owner of each username, keyed by the username in lowercase, so no two players can take
+/// names that differ only by case.
+pub static USERNAME_OWNERS: Keymap<String, CanonicalAddr, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"username_owners")
+        .without_iter()
+        .build();
+/// Each player's hole cards, two in Hold'em and four in Omaha.
+pub static HANDS: Keymap<CanonicalAddr, Vec<u8>, Bincode2, WithoutIter> =
     KeymapBuilder::new(b"hands").without_iter().build();
 pub static BALANCES: Keymap<CanonicalAddr, u128, Bincode2, WithoutIter> =
     KeymapBuilder::new(b"balances").without_iter().build();
+/// Chips bought in with after the game started, which are added to `BALANCES` at the start of
+/// the next hand.
+pub static PENDING_BUY_INS: Keymap<CanonicalAddr, u128, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"pending_buy_ins")
+        .without_iter()
+        .build();
+/// Players who keep their seat and chips, but are not dealt into new hands.
+pub static SITTING_OUT: Keymap<CanonicalAddr, bool, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"sitting_out").without_iter().build();
+/// Players who straddle whenever they are under the gun.
+pub static STRADDLERS: Keymap<CanonicalAddr, bool, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"straddlers").without_iter().build();
+/// Players who show their hand, rather than mucking it, when they win a pot uncontested.
+pub static SHOWS_UNCONTESTED: Keymap<CanonicalAddr, bool, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"shows_uncontested")
+        .without_iter()
+        .build();
+/// Each player's number of consecutive turns that ended in a timeout.
+pub static TIMEOUTS: Keymap<CanonicalAddr, u8, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"timeouts").without_iter().build();
 pub static TABLE: AppendStore<u8> = AppendStore::new(b"table");
 pub static REVEALED_CARDS: Item<u8> = Item::new(b"num_revealed");
+/// Number of hands dealt so far, which also identifies the current hand.
+pub static HAND_ID: Item<u64> = Item::new(b"hand_id");
+/// Everything that happened in each hand, stored under a suffix of the hand ID.
+pub static HAND_HISTORY: AppendStore<HandEvent> = AppendStore::new(b"hand_history");
+/// The shuffled deck and salt of each hand, which are kept secret until the hand is over.
+pub static DECK_PROOFS: Keymap<u64, DeckProof, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"deck_proofs").without_iter().build();
+/// The board and every hand revealed at the end of the last completed hand.
+pub static LAST_SHOWDOWN: Item<LastShowdown> = Item::new(b"last_showdown");
 pub static POT: Item<u128> = Item::new(b"pot");
 pub static CURRENT_MIN_BET: Item<u128> = Item::new(b"min_bet");
+pub static LAST_RAISE_SIZE: Item<u128> = Item::new(b"last_raise");
+/// Number of raises made in the current betting round after its first bet, which fixed limit
+/// games cap.
+pub static STREET_RAISES: Item<u8> = Item::new(b"street_raises");
+pub static HAS_ACTED: Keymap<CanonicalAddr, bool, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"has_acted").without_iter().build();
+/// Each player's total bet for the current street.
 pub static BETS: Keymap<CanonicalAddr, u128, Bincode2, WithoutIter> =
     KeymapBuilder::new(b"bets").without_iter().build();
+/// Chips in the pot that are not part of any player's contribution, such as a big blind ante.
+/// They belong to the main pot, which every player still in the hand is eligible to win.
+pub static DEAD_MONEY: Item<u128> = Item::new(b"dead_money");
+/// Each player's total contribution to the pot over every street of the current hand.
+pub static CONTRIBUTIONS: Keymap<CanonicalAddr, u128, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"contributions").without_iter().build();
 pub static IS_STARTED: Item<bool> = Item::new(b"started");
 pub static ADMIN: Item<CanonicalAddr> = Item::new(b"admin");
+/// The tournament director contract, if the table is one of several in a tournament.
+pub static DIRECTOR: Item<CanonicalAddr> = Item::new(b"director");
+/// A big blind set by the tournament director, which takes effect at the start of the next hand.
+pub static PENDING_BIG_BLIND: Item<u32> = Item::new(b"pending_big_blind");
 pub static CURRENT_TURN_POSITION: Item<u8> = Item::new(b"current_turn");
+/// Block time, in seconds, at which the current player's turn began.
+pub static TURN_STARTED_AT: Item<u64> = Item::new(b"turn_started_at");
 pub static BUTTON_POSITION: Item<u8> = Item::new(b"button_position");
+pub static SMALL_BLIND_POSITION: Item<u8> = Item::new(b"small_blind_position");
+pub static BIG_BLIND_POSITION: Item<u8> = Item::new(b"big_blind_position");
+/// The tournament's current blind level, as an index into its `blind_levels`.
+pub static BLIND_LEVEL: Item<u32> = Item::new(b"blind_level");
+/// Block time, in seconds, at which the tournament started.
+pub static TOURNAMENT_STARTED_AT: Item<u64> = Item::new(b"tournament_started_at");
+/// uSCRT paid in by every player registered for the tournament.
+pub static PRIZE_POOL: Item<u128> = Item::new(b"prize_pool");
+/// Players knocked out of the tournament, in the order they were eliminated.
+pub static ELIMINATED: AppendStore<CanonicalAddr> = AppendStore::new(b"eliminated");
+/// The last player left with chips, once the tournament is over.
+pub static TOURNAMENT_WINNER: Item<CanonicalAddr> = Item::new(b"tournament_winner");
+/// uSCRT won by each player who finished in the money, until they withdraw it.
+pub static PRIZES: Keymap<CanonicalAddr, u128, Bincode2, WithoutIter> =
+    KeymapBuilder::new(b"prizes").without_iter().build();
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LobbyConfig {
     pub big_blind: u32,
     pub max_buy_in_bb: u8,
     pub min_buy_in_bb: u8,
+    /// Number of seconds each player has to act before anyone may claim a timeout against them.
+    pub turn_time_limit: Option<u64>,
+    pub tournament: Option<TournamentConfig>,
+    pub ante: Option<Ante>,
+    pub allow_straddle: bool,
+    pub betting_structure: BettingStructure,
+    pub variant: GameVariant,
+    pub disable_spectators: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -37,6 +126,22 @@ pub struct PreStartState {
     pub lobby_config: LobbyConfig,
     pub is_started: bool,
     pub balances: Vec<(String, u128)>,
+    /// Number of seats taken by players who still have chips at the table.
+    pub occupied_seats: u8,
+    /// Usernames of the players holding cards or with chips in the pot of the current hand.
+    pub players_in_hand: Vec<String>,
+    pub tournament: Option<TournamentState>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TournamentState {
+    pub prize_pool: u128,
+    pub blind_level: u32,
+    pub big_blind: u32,
+    /// Usernames of the players knocked out, in the order they were eliminated.
+    pub eliminated: Vec<String>,
+    /// The prize won by each player who finished in the money, in order of finishing position.
+    pub prizes: Vec<(String, u128)>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -44,56 +149,221 @@ pub struct GameState {
     pub balances: Vec<(String, u128)>,
     pub table: Vec<u8>,
     pub pot: u128,
-    pub hand: Option<(u8, u8)>,
+    /// The hand being played, which can be checked with the `VerifyHand` query once it is over.
+    pub hand_id: u64,
+    pub hand: Option<Vec<u8>>,
     pub current_turn: String,
     pub button_player: String,
     pub min_bet: u128,
+    /// The chips the player has already bet on this street.
+    pub bet: u128,
+    /// The smallest amount the player can raise to without going all in.
+    pub min_raise_to: u128,
+    pub sitting_out: Vec<String>,
+}
+
+/// The game state anyone watching the table can see, which never includes hole cards.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SpectatorState {
+    pub balances: Vec<(String, u128)>,
+    pub table: Vec<u8>,
+    pub pot: u128,
+    pub hand_id: u64,
+    pub current_turn: String,
+    pub button_player: String,
+    /// The most recent fold, check, call or raise in the current hand.
+    pub last_action: Option<HandEvent>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LastShowdown {
+    pub hand_id: u64,
+    /// The cards dealt to the board before the hand ended.
+    pub table: Vec<u8>,
+    /// Hands revealed at showdown, in seat order. A player who won the pot uncontested is only
+    /// listed if they chose to show.
+    pub hands: Vec<ShownHand>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ShownHand {
+    pub player: String,
+    pub hand: Vec<u8>,
+    /// The name of the best hand made with the board, such as "Flush, ace-high", if the board
+    /// had enough cards to make a hand.
+    pub hand_name: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Street {
+    Flop,
+    Turn,
+    River,
+}
+
+/// A single entry in the history of a hand. Players are identified by their username at the time
+/// of the hand, and amounts are in uSCRT.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum HandEvent {
+    /// The players dealt into the hand, as `(seat, username, stack)` before the blinds are posted.
+    Start {
+        /// Block time, in seconds, at which the hand was dealt.
+        time: u64,
+        button_seat: u8,
+        big_blind: u32,
+        variant: GameVariant,
+        betting_structure: BettingStructure,
+        players: Vec<(u8, String, u128)>,
+    },
+    /// An ante posted by the player, either their own or a big blind ante for the whole table.
+    Ante {
+        player: String,
+        amount: u128,
+    },
+    SmallBlind {
+        player: String,
+        amount: u128,
+    },
+    BigBlind {
+        player: String,
+        amount: u128,
+    },
+    /// A blind raise posted by the player under the gun, who acts last before the flop.
+    Straddle {
+        player: String,
+        amount: u128,
+    },
+    Fold {
+        player: String,
+    },
+    Check {
+        player: String,
+    },
+    /// `amount` is the number of chips added to the player's bet.
+    Call {
+        player: String,
+        amount: u128,
+        is_all_in: bool,
+    },
+    /// `to` is the player's total bet for the street.
+    Raise {
+        player: String,
+        to: u128,
+        is_all_in: bool,
+    },
+    /// The cards revealed on a street.
+    Board {
+        street: Street,
+        cards: Vec<u8>,
+    },
+    Show {
+        player: String,
+        hand: Vec<u8>,
+    },
+    /// Chips won from the main pot (pot 0) or a side pot.
+    Win {
+        player: String,
+        amount: u128,
+        pot: u8,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HandHistoryResponse {
+    pub hand_id: u64,
+    pub events: Vec<HandEvent>,
+    pub total_events: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DeckProof {
+    pub commitment: Vec<u8>,
+    pub cards: Vec<u8>,
+    /// Secret seed each card's salt is derived from, which is never revealed.
+    pub salt: Vec<u8>,
+    pub is_revealed: bool,
+    /// Positions in the deck of the cards seen during the hand, which are revealed once it is
+    /// over.
+    pub seen_positions: Vec<u8>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct VerifyHandResponse {
+    pub hand_id: u64,
+    /// SHA-256 hash of every card's commitment in dealing order, published when the hand is
+    /// dealt.
+    pub commitment: Binary,
+    /// The commitment to each card of the shuffled deck in dealing order, once the hand is over.
+    pub card_commitments: Option<Vec<Binary>>,
+    /// The board and any hands shown, once the hand is over. Mucked hands and cards that were
+    /// never dealt stay hidden.
+    pub revealed_cards: Option<Vec<RevealedCard>>,
+    /// Whether the card commitments hash to the commitment, and each revealed card and its salt
+    /// hash to the card's commitment, once the hand is over.
+    pub is_valid: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RevealedCard {
+    /// Position of the card in the shuffled deck, in dealing order.
+    pub position: u8,
+    pub card: u8,
+    pub salt: Binary,
 }
 
 pub struct Deck {
     // Cards are represented with numbers 0..52
     cards: Vec<u8>,
     index: usize,
+    salt: [u8; 32],
 }
 
 impl Deck {
-    pub fn new() -> Self {
+    /// Create a deck shuffled with a Fisher-Yates shuffle. The random numbers are drawn from a
+    /// ChaCha stream seeded with the hash of `seed` and `entropy`, so the same inputs always
+    /// produce the same deck. A short deck leaves out the twos through fives, but every card
+    /// keeps the number it has in a full deck.
+    pub fn shuffled(variant: GameVariant, seed: &[u8], entropy: &[u8]) -> Self {
+        let mut rng = ContractPrng::new(seed, entropy);
+        let mut cards: Vec<u8> = (0..52)
+            .filter(|card| variant != GameVariant::ShortDeck || !(1..5).contains(&(card % 13)))
+            .collect();
+
+        for index in (1..cards.len()).rev() {
+            let swap_index = random_below(&mut rng, index as u32 + 1) as usize;
+            cards.swap(index, swap_index);
+        }
+
+        let salt = rng.rand_bytes();
+
         Self {
-            cards: (0..52).collect(),
+            cards,
             index: 0,
+            salt,
         }
     }
 
-    pub fn draw(&mut self, seed: &[u8]) -> StdResult<u8> {
-        let deck_size = self.cards.len();
-
-        if self.index >= deck_size {
-            return Err(StdError::generic_err(format!(
-                "deck_size: {deck_size}, self.index: {}",
-                self.index,
-            )));
+    /// Build the proof that commits to this deck before any cards are revealed.
+    pub fn proof(&self) -> DeckProof {
+        DeckProof {
+            commitment: deck_commitment(&card_commitments(&self.cards, &self.salt)).to_vec(),
+            cards: self.cards.clone(),
+            salt: self.salt.to_vec(),
+            is_revealed: false,
+            seen_positions: vec![],
         }
+    }
 
-        // Get the next byte from the randomness source.
-        let random_byte = *seed.get(self.index).ok_or(StdError::generic_err(format!(
-            "self.index ({}) out of range for seed (length {})",
-            self.index,
-            seed.len(),
-        )))?;
-
-        // Force the number to be within the size of the deck, excluding used cards.
-        let random_deck_index = random_byte as usize % (deck_size - self.index);
-        let card = *self
-            .cards
-            .get(random_deck_index)
-            .ok_or(StdError::generic_err(format!(
-                "random_deck_index ({random_deck_index}) out of range for self.cards (length {})",
-                deck_size,
-            )))?;
-
-        // Move the card we have just picked to the back of the deck, to prevent reselection.
-        self.cards
-            .swap(random_deck_index, deck_size - 1 - self.index);
+    pub fn draw(&mut self) -> StdResult<u8> {
+        let card = *self.cards.get(self.index).ok_or_else(|| {
+            StdError::generic_err(format!(
+                "deck_size: {}, self.index: {}",
+                self.cards.len(),
+                self.index,
+            ))
+        })?;
 
         self.index += 1;
 
@@ -101,6 +371,55 @@ impl Deck {
     }
 }
 
+/// The salt of the card at `position` in the deck, derived from the deck's secret seed. Each card
+/// has its own salt, so revealing some cards says nothing about the rest of the deck.
+pub fn card_salt(seed: &[u8], position: u8) -> [u8; 32] {
+    sha_256(&[seed, &[position]].concat())
+}
+
+/// Hash a card together with its salt. Without the salt, the commitment to a card could be
+/// brute forced.
+pub fn card_commitment(card: u8, salt: &[u8]) -> [u8; 32] {
+    sha_256(&[&[card], salt].concat())
+}
+
+/// The commitment to each card of a shuffled deck, in dealing order.
+pub fn card_commitments(cards: &[u8], seed: &[u8]) -> Vec<[u8; 32]> {
+    cards
+        .iter()
+        .enumerate()
+        .map(|(position, card)| card_commitment(*card, &card_salt(seed, position as u8)))
+        .collect()
+}
+
+/// Hash the commitments to every card of a shuffled deck, in dealing order.
+pub fn deck_commitment(card_commitments: &[[u8; 32]]) -> [u8; 32] {
+    sha_256(&card_commitments.concat())
+}
+
+/// Pick a uniformly random number in `0..upper_bound`. Random numbers from the incomplete range
+/// at the top of `u32` are rejected, as reducing them would bias the result towards small numbers.
+fn random_below(rng: &mut impl RngCore, upper_bound: u32) -> u32 {
+    let range = 1u64 << 32;
+    let limit = range - range % upper_bound as u64;
+
+    loop {
+        let random_number = rng.next_u32() as u64;
+        if random_number < limit {
+            return (random_number % upper_bound as u64) as u32;
+        }
+    }
+}
+
+/// Whether the seat can be given to a new player, as the player sitting there has no chips left
+/// at the table.
+pub fn is_seat_vacant(address: &CanonicalAddr, storage: &dyn Storage) -> bool {
+    !BALANCES.contains(storage, address)
+        && !PENDING_BUY_INS.contains(storage, address)
+        && !HANDS.contains(storage, address)
+        && !CONTRIBUTIONS.contains(storage, address)
+}
+
 pub fn get_balances(addresses: &[CanonicalAddr], deps: Deps) -> Vec<(String, u128)> {
     addresses
         .iter()
@@ -111,3 +430,13 @@ pub fn get_balances(addresses: &[CanonicalAddr], deps: Deps) -> Vec<(String, u12
         })
         .collect()
 }
+
+/// Whether the username follows the naming rules, regardless of whether it is taken.
+pub fn is_valid_username(username: &str) -> bool {
+    let is_valid_length = (MIN_USERNAME_LENGTH..=MAX_USERNAME_LENGTH).contains(&username.len());
+    let is_valid_charset = username
+        .chars()
+        .all(|character| character.is_ascii_alphanumeric() || character == '_' || character == '-');
+
+    is_valid_length && is_valid_charset
+}
@@ -0,0 +1,41 @@
+//! Fresh mock dependencies for the integration tests of this contract and the contracts that
+//! build on it.
+
+use std::sync::{Mutex, MutexGuard};
+
+use cosmwasm_std::{
+    testing::{mock_dependencies, MockApi, MockQuerier, MockStorage},
+    OwnedDeps, Storage,
+};
+
+use crate::state::{ALL_PLAYERS, ELIMINATED, TABLE};
+
+/// Append stores cache their length in their static, which is shared by every test in the
+/// process, so only one set of dependencies is in use at a time.
+static STORAGE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Empty mock dependencies, after `clear` has reset every append store the test will use. The
+/// dependencies must only be used while the returned guard is held.
+pub fn isolated_deps(
+    clear: impl FnOnce(&mut dyn Storage),
+) -> (
+    OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    MutexGuard<'static, ()>,
+) {
+    let lock = STORAGE_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut deps = mock_dependencies();
+
+    // Clearing each append store resets its cached length to match the empty storage.
+    clear(&mut deps.storage);
+
+    (deps, lock)
+}
+
+/// Reset the append stores of a table.
+pub fn clear_table_stores(storage: &mut dyn Storage) {
+    ALL_PLAYERS.clear(storage);
+    TABLE.clear(storage);
+    ELIMINATED.clear(storage);
+}
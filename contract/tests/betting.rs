@@ -0,0 +1,209 @@
+mod common;
+
+use chain_poker::{
+    error::ContractError,
+    msg::{BettingStructure, ExecuteMsg, InstantiateMsg},
+    state::HandEvent,
+};
+use common::{lobby, Table};
+use cosmwasm_std::Uint128;
+
+fn heads_up(betting_structure: BettingStructure) -> Table {
+    let msg = InstantiateMsg {
+        betting_structure,
+        ..lobby()
+    };
+    Table::start(msg, &[("alice", 5000), ("bobby", 5000)])
+}
+
+fn raise(to: u128) -> ExecuteMsg {
+    ExecuteMsg::Raise {
+        to: Uint128::new(to),
+    }
+}
+
+#[test]
+fn short_all_in_does_not_reopen_the_action() {
+    let mut table = Table::start(lobby(), &[("alice", 5000), ("bobby", 5000), ("carol", 250)]);
+
+    // Carol's all in raises the bet by only 50, less than the last raise of 100.
+    table.execute("alice", raise(200)).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    table.execute("carol", ExecuteMsg::AllIn {}).unwrap();
+
+    assert_eq!(
+        table.execute("alice", raise(500)).unwrap_err(),
+        ContractError::ActionNotReopened
+    );
+    assert_eq!(
+        table.execute("alice", ExecuteMsg::AllIn {}).unwrap_err(),
+        ContractError::ActionNotReopened
+    );
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+
+    assert_eq!(
+        table.execute("bobby", raise(500)).unwrap_err(),
+        ContractError::ActionNotReopened
+    );
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    assert_eq!(table.balance("alice"), 4750);
+    assert_eq!(table.balance("bobby"), 4750);
+}
+
+#[test]
+fn full_all_in_raise_reopens_the_action() {
+    let mut table = Table::start(lobby(), &[("alice", 5000), ("bobby", 5000), ("carol", 300)]);
+
+    // Carol's all in raises the bet by 100, as much as the last raise.
+    table.execute("alice", raise(200)).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    table.execute("carol", ExecuteMsg::AllIn {}).unwrap();
+
+    table.execute("alice", raise(400)).unwrap();
+    assert_eq!(table.current_turn(), "bobby");
+}
+
+#[test]
+fn pot_limit_raises_are_at_most_the_pot_after_calling() {
+    let mut table = heads_up(BettingStructure::PotLimit);
+
+    // Alice calls 50 to make the pot 200, and may raise it by that much on top of the big blind.
+    assert_eq!(
+        table.execute("alice", raise(301)).unwrap_err(),
+        ContractError::BetAboveMaximum { max_raise_to: 300 }
+    );
+    table.execute("alice", raise(300)).unwrap();
+
+    // Bobby calls 200 to make the pot 600. Going all in is limited to the pot too.
+    assert_eq!(
+        table.execute("bobby", ExecuteMsg::AllIn {}).unwrap_err(),
+        ContractError::BetAboveMaximum { max_raise_to: 900 }
+    );
+    assert_eq!(
+        table.execute("bobby", raise(901)).unwrap_err(),
+        ContractError::BetAboveMaximum { max_raise_to: 900 }
+    );
+    table.execute("bobby", raise(900)).unwrap();
+}
+
+#[test]
+fn fixed_limit_bets_are_the_bet_size_for_the_street() {
+    let mut table = heads_up(BettingStructure::FixedLimit { raise_cap: 4 });
+
+    for to in [150, 300] {
+        assert_eq!(
+            table.execute("alice", raise(to)).unwrap_err(),
+            ContractError::FixedBetSize { raise_to: 200 }
+        );
+    }
+    table.execute("alice", raise(200)).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+
+    // On the flop, bets are still one big blind.
+    assert_eq!(
+        table.execute("bobby", raise(200)).unwrap_err(),
+        ContractError::FixedBetSize { raise_to: 100 }
+    );
+    table.execute("bobby", raise(100)).unwrap();
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+
+    // On the turn, bets are two big blinds.
+    assert_eq!(
+        table.execute("bobby", raise(100)).unwrap_err(),
+        ContractError::FixedBetSize { raise_to: 200 }
+    );
+    table.execute("bobby", raise(200)).unwrap();
+    table.execute("alice", raise(400)).unwrap();
+}
+
+#[test]
+fn fixed_limit_raises_are_capped_with_the_big_blind_as_the_first_bet() {
+    let mut table = heads_up(BettingStructure::FixedLimit { raise_cap: 3 });
+
+    // The big blind is the first bet, so alice's raise to 200 is the first of the three raises.
+    table.execute("alice", raise(200)).unwrap();
+    table.execute("bobby", raise(300)).unwrap();
+    table.execute("alice", raise(400)).unwrap();
+    assert_eq!(
+        table.execute("bobby", raise(500)).unwrap_err(),
+        ContractError::RaiseCapReached
+    );
+    assert_eq!(
+        table.execute("bobby", ExecuteMsg::AllIn {}).unwrap_err(),
+        ContractError::RaiseCapReached
+    );
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+
+    // The cap resets on the flop, where the first bet is not a raise.
+    table.execute("bobby", raise(100)).unwrap();
+    table.execute("alice", raise(200)).unwrap();
+    table.execute("bobby", raise(300)).unwrap();
+    table.execute("alice", raise(400)).unwrap();
+    assert_eq!(
+        table.execute("bobby", raise(500)).unwrap_err(),
+        ContractError::RaiseCapReached
+    );
+}
+
+#[test]
+fn actions_are_checked_against_the_bet_faced() {
+    let mut table = Table::start(
+        lobby(),
+        &[("alice", 5000), ("bobby", 5000), ("carol", 5000)],
+    );
+
+    // Alice is first to act, facing the big blind.
+    assert_eq!(
+        table.execute("bobby", ExecuteMsg::Call {}).unwrap_err(),
+        ContractError::NotYourTurn
+    );
+    assert_eq!(
+        table.execute("alice", ExecuteMsg::Check {}).unwrap_err(),
+        ContractError::CannotCheck { to_call: 100 }
+    );
+    for to in [100, 150] {
+        assert_eq!(
+            table.execute("alice", raise(to)).unwrap_err(),
+            ContractError::BetBelowMinimum { min_raise_to: 200 }
+        );
+    }
+    assert_eq!(
+        table.execute("alice", raise(5001)).unwrap_err(),
+        ContractError::InsufficientChips
+    );
+    assert_eq!(table.current_turn(), "alice");
+
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+
+    // The big blind has already matched the bet.
+    assert_eq!(
+        table.execute("carol", ExecuteMsg::Call {}).unwrap_err(),
+        ContractError::NothingToCall
+    );
+    table.execute("carol", ExecuteMsg::Check {}).unwrap();
+
+    assert_eq!(
+        table.execute("alice", ExecuteMsg::Fold {}).unwrap_err(),
+        ContractError::NotYourTurn
+    );
+}
+
+#[test]
+fn all_in_bets_the_whole_stack() {
+    let mut table = Table::start(
+        lobby(),
+        &[("alice", 5000), ("bobby", 5000), ("carol", 5000)],
+    );
+
+    assert_eq!(
+        table.execute("bobby", ExecuteMsg::AllIn {}).unwrap_err(),
+        ContractError::NotYourTurn
+    );
+    table.execute("alice", ExecuteMsg::AllIn {}).unwrap();
+    assert_eq!(table.balance("alice"), 0);
+    assert!(table.events(1).iter().any(|event| matches!(
+        event,
+        HandEvent::Raise { player, to: 5000, is_all_in: true } if player == "alice"
+    )));
+}
@@ -0,0 +1,132 @@
+mod common;
+
+use chain_poker::{msg::ExecuteMsg, state::HandEvent};
+use common::{lobby, Table};
+
+#[test]
+fn players_sitting_out_do_not_post_the_small_blind() {
+    let mut table = Table::start(
+        lobby(),
+        &[
+            ("alice", 5000),
+            ("bobby", 5000),
+            ("carol", 5000),
+            ("david", 5000),
+        ],
+    );
+
+    // Carol sits out while in the big blind, and wins the blinds when everyone folds to her.
+    table.execute("carol", ExecuteMsg::SitOut {}).unwrap();
+    for player in ["david", "alice", "bobby"] {
+        assert_eq!(table.current_turn(), player);
+        table.execute(player, ExecuteMsg::Fold {}).unwrap();
+    }
+    assert_eq!(table.balance("carol"), 5050);
+
+    // The small blind would move to carol's seat, so it is dead for the next hand.
+    let events = table.events(table.hand_id());
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, HandEvent::SmallBlind { .. })));
+    assert!(events.iter().any(|event| matches!(
+        event,
+        HandEvent::BigBlind { player, amount: 100 } if player == "david"
+    )));
+    assert_eq!(table.balance("carol"), 5050);
+    assert_eq!(
+        table.pre_start_state().players_in_hand,
+        vec!["alice", "bobby", "david"]
+    );
+}
+
+fn blinds(table: &Table) -> (Option<String>, String) {
+    let events = table.events(table.hand_id());
+    let small_blind = events.iter().find_map(|event| match event {
+        HandEvent::SmallBlind { player, .. } => Some(player.clone()),
+        _ => None,
+    });
+    let big_blind = events.iter().find_map(|event| match event {
+        HandEvent::BigBlind { player, .. } => Some(player.clone()),
+        _ => None,
+    });
+    (small_blind, big_blind.unwrap())
+}
+
+#[test]
+fn heads_up_button_posts_the_small_blind_and_acts_first_before_the_flop() {
+    let mut table = Table::start(lobby(), &[("alice", 5000), ("bobby", 5000)]);
+    assert_eq!(
+        blinds(&table),
+        (Some("alice".to_string()), "bobby".to_string())
+    );
+
+    assert_eq!(table.current_turn(), "alice");
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Check {}).unwrap();
+
+    // After the flop, the big blind acts first.
+    assert_eq!(table.current_turn(), "bobby");
+    table.execute("bobby", ExecuteMsg::Check {}).unwrap();
+    table.execute("alice", ExecuteMsg::Fold {}).unwrap();
+
+    // The button moves to bobby, who posts the small blind and acts first.
+    assert_eq!(
+        blinds(&table),
+        (Some("bobby".to_string()), "alice".to_string())
+    );
+    assert_eq!(table.current_turn(), "bobby");
+}
+
+#[test]
+fn blinds_move_on_past_a_busted_player() {
+    let mut table = Table::start(
+        lobby(),
+        &[
+            ("alice", 5000),
+            ("bobby", 100),
+            ("carol", 5000),
+            ("david", 5000),
+        ],
+    );
+    // 8c Qs 7d 4h 2s
+    table.set_cards(
+        &[
+            ("alice", &[30, 31]),
+            ("bobby", &[8, 20]),
+            ("carol", &[23, 9]),
+            ("david", &[0, 13]),
+        ],
+        [33, 50, 19, 3, 40],
+    );
+
+    // Bobby goes all in from the small blind, and loses to david's aces.
+    assert_eq!(
+        blinds(&table),
+        (Some("bobby".to_string()), "carol".to_string())
+    );
+    table.execute("david", ExecuteMsg::Call {}).unwrap();
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+    table.execute("bobby", ExecuteMsg::AllIn {}).unwrap();
+    table.execute("carol", ExecuteMsg::Check {}).unwrap();
+    while table.hand_id() == 1 {
+        let player = table.current_turn();
+        table.execute(&player, ExecuteMsg::Check {}).unwrap();
+    }
+    assert_eq!(table.balance("bobby"), 0);
+
+    // The big blind moves on to david, and carol posts the small blind. The button moves to
+    // bobby's empty seat, leaving a dead button, and alice acts first before the flop.
+    assert_eq!(
+        blinds(&table),
+        (Some("carol".to_string()), "david".to_string())
+    );
+    let Some(HandEvent::Start { button_seat, .. }) = table.events(2).into_iter().next() else {
+        panic!("the next hand was not dealt");
+    };
+    assert_eq!(button_seat, 1);
+    assert_eq!(table.current_turn(), "alice");
+    assert_eq!(
+        table.pre_start_state().players_in_hand,
+        vec!["alice", "carol", "david"]
+    );
+}
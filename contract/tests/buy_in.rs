@@ -0,0 +1,17 @@
+mod common;
+
+use chain_poker::{error::ContractError, msg::ExecuteMsg};
+use common::{lobby, Table};
+
+#[test]
+fn chips_in_the_pot_count_towards_the_maximum_buy_in() {
+    let mut table = Table::start(lobby(), &[("alice", 10000), ("bobby", 10000)]);
+
+    // Alice has no chips left behind, but the whole maximum buy in is in the pot.
+    table.execute("alice", ExecuteMsg::AllIn {}).unwrap();
+    assert_eq!(table.balance("alice"), 0);
+    assert_eq!(
+        table.buy_in("alice", 10000).unwrap_err(),
+        ContractError::BuyInAboveMaximum { maximum: 0 }
+    );
+}
@@ -0,0 +1,200 @@
+//! Runs the contract against mock dependencies, with each player's username matching their
+//! address.
+#![allow(dead_code)]
+
+use std::sync::MutexGuard;
+
+use chain_poker::{
+    contract::{execute, instantiate, query},
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    poker::history::hand_history,
+    state::{
+        GameState, HandEvent, PreStartState, SpectatorState, ALL_PLAYERS, BALANCES,
+        CURRENT_TURN_POSITION, HANDS, HAND_ID, SITTING_OUT, TABLE, USERNAMES,
+    },
+    test_support::{clear_table_stores, isolated_deps},
+};
+use cosmwasm_std::{
+    coins, from_binary,
+    testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+    Addr, Api, Binary, Env, OwnedDeps, Response,
+};
+use secret_toolkit::permit::{Permit, PermitParams, PermitSignature, PubKey, TokenPermissions};
+
+pub fn env() -> Env {
+    let mut env = mock_env();
+    env.block.random = Some(Binary::from([7; 32]));
+    env
+}
+
+/// The account that signed `permit`, which can buy in to see its own game state.
+pub const PERMIT_HOLDER: &str = "secret1399pyvvk3hvwgxwt3udkslsc5jl3rqv4yshfrl";
+/// The only contract address `permit` is valid for.
+const PERMIT_CONTRACT: &str = "secret1rf03820fp8gngzg2w02vd30ns78qkc8rg8dxaq";
+
+/// A query permit signed by `PERMIT_HOLDER`, taken from secret-toolkit's own tests.
+pub fn permit() -> Permit {
+    Permit {
+        params: PermitParams {
+            allowed_tokens: vec![PERMIT_CONTRACT.to_string()],
+            permit_name: format!("memo_{PERMIT_CONTRACT}"),
+            chain_id: "pulsar-2".to_string(),
+            permissions: vec![TokenPermissions::History],
+        },
+        signature: PermitSignature {
+            pub_key: PubKey {
+                r#type: "tendermint/PubKeySecp256k1".to_string(),
+                value: Binary::from_base64("A5M49l32ZrV+SDsPnoRv8fH7ivNC4gEX9prvd4RwvRaL").unwrap(),
+            },
+            signature: Binary::from_base64(
+                "hw/Mo3ZZYu1pEiDdymElFkuCuJzg9soDHw+4DxK7cL9rafiyykh7VynS+guotRAKXhfYMwCiyWmiznc6R+UlsQ==",
+            )
+            .unwrap(),
+        },
+    }
+}
+
+/// A cash game lobby with a big blind of 100, created by alice.
+pub fn lobby() -> InstantiateMsg {
+    InstantiateMsg {
+        username: "alice".to_string(),
+        big_blind: 100,
+        max_buy_in_bb: 100,
+        min_buy_in_bb: 1,
+        turn_time_limit: None,
+        admin: None,
+        tournament: None,
+        director: None,
+        ante: None,
+        allow_straddle: false,
+        betting_structure: Default::default(),
+        variant: Default::default(),
+        disable_spectators: false,
+    }
+}
+
+pub struct Table {
+    pub deps: OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    _lock: MutexGuard<'static, ()>,
+}
+
+impl Table {
+    pub fn new(msg: InstantiateMsg) -> Self {
+        let (mut deps, lock) = isolated_deps(clear_table_stores);
+        instantiate(deps.as_mut(), env(), mock_info("alice", &[]), msg).unwrap();
+
+        Self { deps, _lock: lock }
+    }
+
+    /// Seat each player with their stack, then deal the first hand with the first player on the
+    /// button.
+    pub fn start(msg: InstantiateMsg, stacks: &[(&str, u128)]) -> Self {
+        let mut table = Self::new(msg);
+        for (player, stack) in stacks {
+            table.buy_in(player, *stack).unwrap();
+        }
+        table.execute("alice", ExecuteMsg::StartGame {}).unwrap();
+        table
+    }
+
+    pub fn execute(&mut self, player: &str, msg: ExecuteMsg) -> Result<Response, ContractError> {
+        execute(self.deps.as_mut(), env(), mock_info(player, &[]), msg)
+    }
+
+    /// Execute the message the given number of seconds after every other message.
+    pub fn execute_later(
+        &mut self,
+        seconds: u64,
+        player: &str,
+        msg: ExecuteMsg,
+    ) -> Result<Response, ContractError> {
+        let mut env = env();
+        env.block.time = env.block.time.plus_seconds(seconds);
+        execute(self.deps.as_mut(), env, mock_info(player, &[]), msg)
+    }
+
+    pub fn buy_in(&mut self, player: &str, amount: u128) -> Result<Response, ContractError> {
+        self.buy_in_as(player, player, amount)
+    }
+
+    /// Buy in with a username that differs from the player's address.
+    pub fn buy_in_as(
+        &mut self,
+        player: &str,
+        username: &str,
+        amount: u128,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::BuyIn {
+            username: username.to_string(),
+        };
+        execute(
+            self.deps.as_mut(),
+            env(),
+            mock_info(player, &coins(amount, "uscrt")),
+            msg,
+        )
+    }
+
+    pub fn pre_start_state(&self) -> PreStartState {
+        let response = query(self.deps.as_ref(), env(), QueryMsg::ViewPreStartState {}).unwrap();
+        from_binary(&response).unwrap()
+    }
+
+    pub fn spectator_state(&self) -> Result<SpectatorState, ContractError> {
+        let response = query(self.deps.as_ref(), env(), QueryMsg::SpectateGame {})?;
+        Ok(from_binary(&response).unwrap())
+    }
+
+    /// The game state as seen by `PERMIT_HOLDER`.
+    pub fn game_state(&self) -> GameState {
+        let mut env = env();
+        env.contract.address = Addr::unchecked(PERMIT_CONTRACT);
+        let msg = QueryMsg::ViewGameState { permit: permit() };
+        from_binary(&query(self.deps.as_ref(), env, msg).unwrap()).unwrap()
+    }
+
+    pub fn balance(&self, player: &str) -> u128 {
+        let address = self.deps.api.addr_canonicalize(player).unwrap();
+        BALANCES.get(&self.deps.storage, &address).unwrap_or(0)
+    }
+
+    pub fn is_sitting_out(&self, player: &str) -> bool {
+        let address = self.deps.api.addr_canonicalize(player).unwrap();
+        SITTING_OUT.contains(&self.deps.storage, &address)
+    }
+
+    pub fn current_turn(&self) -> String {
+        let position = CURRENT_TURN_POSITION.load(&self.deps.storage).unwrap();
+        let address = ALL_PLAYERS
+            .get_at(&self.deps.storage, position as u32)
+            .unwrap();
+        USERNAMES.get(&self.deps.storage, &address).unwrap()
+    }
+
+    pub fn hand_id(&self) -> u64 {
+        HAND_ID.load(&self.deps.storage).unwrap()
+    }
+
+    pub fn events(&self, hand_id: u64) -> Vec<HandEvent> {
+        hand_history(hand_id)
+            .iter(&self.deps.storage)
+            .unwrap()
+            .flatten()
+            .collect()
+    }
+
+    /// Replace the dealt cards, so the result of the showdown is known.
+    pub fn set_cards(&mut self, hands: &[(&str, &[u8])], board: [u8; 5]) {
+        for (player, hand) in hands {
+            let address = self.deps.api.addr_canonicalize(player).unwrap();
+            HANDS
+                .insert(&mut self.deps.storage, &address, &hand.to_vec())
+                .unwrap();
+        }
+        TABLE.clear(&mut self.deps.storage);
+        for card in board {
+            TABLE.push(&mut self.deps.storage, &card).unwrap();
+        }
+    }
+}
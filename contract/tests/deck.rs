@@ -0,0 +1,192 @@
+mod common;
+
+use chain_poker::{
+    contract::query,
+    msg::{ExecuteMsg, GameVariant, QueryMsg},
+    state::{
+        card_commitment, card_commitments, card_salt, deck_commitment, Deck, VerifyHandResponse,
+        LAST_SHOWDOWN,
+    },
+};
+use common::{env, lobby, Table};
+use cosmwasm_std::from_binary;
+
+const DECK_SIZE: usize = 52;
+const SHUFFLES: usize = DECK_SIZE * 400;
+
+/// Critical value of the chi-squared distribution with 51 degrees of freedom at p = 0.001.
+const CHI_SQUARED_CRITICAL_VALUE: f64 = 87.97;
+
+fn shuffle(hand_id: u64) -> Vec<u8> {
+    let mut deck = Deck::shuffled(
+        GameVariant::Holdem,
+        b"block random seed",
+        &hand_id.to_be_bytes(),
+    );
+    (0..DECK_SIZE).map(|_| deck.draw().unwrap()).collect()
+}
+
+fn chi_squared(counts: &[usize]) -> f64 {
+    let expected = SHUFFLES as f64 / counts.len() as f64;
+    counts
+        .iter()
+        .map(|&count| (count as f64 - expected).powi(2) / expected)
+        .sum()
+}
+
+#[test]
+fn deck_contains_every_card_once() {
+    for hand_id in 0..100 {
+        let mut cards = shuffle(hand_id);
+        cards.sort_unstable();
+        assert_eq!(cards, (0..DECK_SIZE as u8).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn short_deck_has_no_twos_through_fives() {
+    let mut deck = Deck::shuffled(GameVariant::ShortDeck, b"block random seed", &[]);
+    let mut cards: Vec<u8> = (0..36).map(|_| deck.draw().unwrap()).collect();
+    assert!(deck.draw().is_err());
+
+    cards.sort_unstable();
+    let expected: Vec<u8> = (0..52)
+        .filter(|card| !(1..5).contains(&(card % 13)))
+        .collect();
+    assert_eq!(cards, expected);
+}
+
+#[test]
+fn deck_runs_out_after_52_cards() {
+    let mut deck = Deck::shuffled(GameVariant::Holdem, b"block random seed", &[]);
+    (0..DECK_SIZE).for_each(|_| {
+        deck.draw().unwrap();
+    });
+    assert!(deck.draw().is_err());
+}
+
+#[test]
+fn shuffle_is_deterministic() {
+    assert_eq!(shuffle(7), shuffle(7));
+    assert_ne!(shuffle(7), shuffle(8));
+}
+
+#[test]
+fn every_card_is_equally_likely_to_be_dealt_first() {
+    let mut counts = [0; DECK_SIZE];
+    for hand_id in 0..SHUFFLES as u64 {
+        counts[shuffle(hand_id)[0] as usize] += 1;
+    }
+
+    let statistic = chi_squared(&counts);
+    assert!(statistic < CHI_SQUARED_CRITICAL_VALUE, "{statistic}");
+}
+
+#[test]
+fn every_card_is_equally_likely_to_be_dealt_last() {
+    let mut counts = [0; DECK_SIZE];
+    for hand_id in 0..SHUFFLES as u64 {
+        counts[shuffle(hand_id)[DECK_SIZE - 1] as usize] += 1;
+    }
+
+    let statistic = chi_squared(&counts);
+    assert!(statistic < CHI_SQUARED_CRITICAL_VALUE, "{statistic}");
+}
+
+#[test]
+fn a_card_is_equally_likely_to_be_in_any_position() {
+    let mut counts = [0; DECK_SIZE];
+    for hand_id in 0..SHUFFLES as u64 {
+        let position = shuffle(hand_id).iter().position(|&card| card == 0).unwrap();
+        counts[position] += 1;
+    }
+
+    let statistic = chi_squared(&counts);
+    assert!(statistic < CHI_SQUARED_CRITICAL_VALUE, "{statistic}");
+}
+
+#[test]
+fn proof_commits_to_the_deck() {
+    let proof = Deck::shuffled(GameVariant::Holdem, b"block random seed", &[]).proof();
+    assert!(!proof.is_revealed);
+    assert_eq!(
+        deck_commitment(&card_commitments(&proof.cards, &proof.salt)).to_vec(),
+        proof.commitment
+    );
+
+    let mut other_cards = proof.cards.clone();
+    other_cards.swap(0, 1);
+    assert_ne!(
+        deck_commitment(&card_commitments(&other_cards, &proof.salt)).to_vec(),
+        proof.commitment
+    );
+}
+
+#[test]
+fn each_card_is_committed_to_with_its_own_salt() {
+    let proof = Deck::shuffled(GameVariant::Holdem, b"block random seed", &[]).proof();
+    let commitments = card_commitments(&proof.cards, &proof.salt);
+
+    let salt = card_salt(&proof.salt, 3);
+    assert_eq!(card_commitment(proof.cards[3], &salt), commitments[3]);
+    assert_ne!(card_commitment(proof.cards[4], &salt), commitments[3]);
+    assert_ne!(card_salt(&proof.salt, 4), salt);
+}
+
+fn verify_hand(table: &Table, hand_id: u64) -> VerifyHandResponse {
+    let response = query(table.deps.as_ref(), env(), QueryMsg::VerifyHand { hand_id }).unwrap();
+    from_binary(&response).unwrap()
+}
+
+#[test]
+fn mucked_hands_are_not_revealed() {
+    let mut table = Table::start(lobby(), &[("alice", 5000), ("bobby", 5000)]);
+
+    let response = verify_hand(&table, 1);
+    assert!(response.card_commitments.is_none());
+    assert!(response.revealed_cards.is_none());
+
+    // Heads up, the button acts first before the flop.
+    table.execute("alice", ExecuteMsg::Fold {}).unwrap();
+
+    let response = verify_hand(&table, 1);
+    assert_eq!(response.card_commitments.unwrap().len(), 52);
+    assert!(response.revealed_cards.unwrap().is_empty());
+    assert_eq!(response.is_valid, Some(true));
+}
+
+#[test]
+fn the_board_and_shown_hands_are_revealed() {
+    let mut table = Table::start(lobby(), &[("alice", 5000), ("bobby", 5000)]);
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+    while table.hand_id() == 1 {
+        let player = table.current_turn();
+        table.execute(&player, ExecuteMsg::Check {}).unwrap();
+    }
+
+    let last_showdown = LAST_SHOWDOWN.load(&table.deps.storage).unwrap();
+    let mut shown_cards: Vec<u8> = last_showdown.table;
+    for shown in last_showdown.hands {
+        shown_cards.extend(shown.hand);
+    }
+    shown_cards.sort_unstable();
+
+    let response = verify_hand(&table, 1);
+    let card_commitments = response.card_commitments.unwrap();
+    let revealed_cards = response.revealed_cards.unwrap();
+    let mut revealed: Vec<u8> = revealed_cards
+        .iter()
+        .map(|revealed| revealed.card)
+        .collect();
+    revealed.sort_unstable();
+    assert_eq!(revealed, shown_cards);
+    assert_eq!(revealed.len(), 9);
+
+    for revealed in revealed_cards {
+        assert_eq!(
+            card_commitment(revealed.card, &revealed.salt).to_vec(),
+            card_commitments[revealed.position as usize].to_vec()
+        );
+    }
+    assert_eq!(response.is_valid, Some(true));
+}
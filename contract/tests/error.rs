@@ -0,0 +1,51 @@
+use chain_poker::{
+    error::ContractError,
+    state::{MAX_SEATS, MAX_USERNAME_LENGTH, MIN_USERNAME_LENGTH},
+};
+use cosmwasm_std::StdError;
+
+#[test]
+fn errors_are_displayed_with_their_code() {
+    assert_eq!(
+        ContractError::NotYourTurn.to_string(),
+        "not_your_turn: It is not your turn to bet"
+    );
+    assert_eq!(
+        ContractError::BetBelowMinimum { min_raise_to: 400 }.to_string(),
+        "bet_below_minimum: You must raise to at least 400 uSCRT"
+    );
+    assert!(ContractError::from(StdError::generic_err("oops"))
+        .to_string()
+        .starts_with("std_error: "));
+}
+
+#[test]
+fn amounts_are_given_in_uscrt() {
+    let errors = [
+        ContractError::BuyInBelowMinimum { minimum: 7 },
+        ContractError::BuyInAboveMaximum { maximum: 7 },
+        ContractError::CannotCheck { to_call: 7 },
+        ContractError::BetBelowMinimum { min_raise_to: 7 },
+        ContractError::TournamentBuyIn { buy_in: 7 },
+        ContractError::BetAboveMaximum { max_raise_to: 7 },
+        ContractError::FixedBetSize { raise_to: 7 },
+    ];
+
+    for error in errors {
+        assert!(error.to_string().contains(" 7 uSCRT"), "{error}");
+    }
+}
+
+#[test]
+fn limits_are_included_in_the_description() {
+    assert_eq!(
+        ContractError::InvalidUsername.to_string(),
+        format!(
+            "invalid_username: Usernames must be {MIN_USERNAME_LENGTH} to {MAX_USERNAME_LENGTH} \
+            characters long, using only letters, numbers, underscores and hyphens"
+        )
+    );
+    assert!(ContractError::InvalidPayouts
+        .to_string()
+        .contains(&format!("between 1 and {MAX_SEATS} places")));
+}
@@ -0,0 +1,177 @@
+use chain_poker::{
+    msg::{BettingStructure, GameVariant},
+    pokerstars::format_hand,
+    state::{HandEvent, Street},
+};
+
+#[test]
+fn formats_a_hand_that_reaches_showdown() {
+    let events = vec![
+        HandEvent::Start {
+            time: 1_700_000_000,
+            button_seat: 0,
+            big_blind: 100,
+            variant: GameVariant::Holdem,
+            betting_structure: BettingStructure::NoLimit,
+            players: vec![(0, "alice".into(), 5000), (1, "bob".into(), 2000)],
+        },
+        HandEvent::SmallBlind {
+            player: "alice".into(),
+            amount: 50,
+        },
+        HandEvent::BigBlind {
+            player: "bob".into(),
+            amount: 100,
+        },
+        HandEvent::Raise {
+            player: "alice".into(),
+            to: 300,
+            is_all_in: false,
+        },
+        HandEvent::Call {
+            player: "bob".into(),
+            amount: 200,
+            is_all_in: false,
+        },
+        HandEvent::Board {
+            street: Street::Flop,
+            cards: vec![0, 14, 28],
+        },
+        HandEvent::Raise {
+            player: "bob".into(),
+            to: 1700,
+            is_all_in: true,
+        },
+        HandEvent::Call {
+            player: "alice".into(),
+            amount: 1700,
+            is_all_in: false,
+        },
+        HandEvent::Board {
+            street: Street::Turn,
+            cards: vec![42],
+        },
+        HandEvent::Board {
+            street: Street::River,
+            cards: vec![12],
+        },
+        HandEvent::Show {
+            player: "alice".into(),
+            hand: vec![9, 22],
+        },
+        HandEvent::Show {
+            player: "bob".into(),
+            hand: vec![13, 39],
+        },
+        HandEvent::Win {
+            player: "bob".into(),
+            amount: 4000,
+            pot: 0,
+        },
+    ];
+
+    let expected = "\
+PokerStars Hand #3: Hold'em No Limit (50/100) - 2023/11/14 22:13:20 UTC
+Table 'Chain Poker' 9-max Seat #1 is the button
+Seat 1: alice (5000 in chips)
+Seat 2: bob (2000 in chips)
+alice: posts small blind 50
+bob: posts big blind 100
+*** HOLE CARDS ***
+alice: raises 200 to 300
+bob: calls 200
+*** FLOP *** [Ah 2d 3c]
+bob: bets 1700 and is all-in
+alice: calls 1700
+*** TURN *** [Ah 2d 3c] [4s]
+*** RIVER *** [Ah 2d 3c 4s] [Kh]
+*** SHOW DOWN ***
+alice: shows [Th Td]
+bob: shows [Ad As]
+bob collected 4000 from pot
+*** SUMMARY ***
+Total pot 4000 | Rake 0
+Board [Ah 2d 3c 4s Kh]";
+
+    assert_eq!(format_hand(3, &events), expected);
+}
+
+#[test]
+fn formats_antes_and_a_straddle_before_the_hole_cards() {
+    let events = vec![
+        HandEvent::Start {
+            time: 1_700_000_000,
+            button_seat: 0,
+            big_blind: 100,
+            variant: GameVariant::Holdem,
+            betting_structure: BettingStructure::NoLimit,
+            players: vec![
+                (0, "alice".into(), 5000),
+                (1, "bob".into(), 5000),
+                (2, "carol".into(), 5000),
+            ],
+        },
+        HandEvent::Ante {
+            player: "alice".into(),
+            amount: 10,
+        },
+        HandEvent::Ante {
+            player: "bob".into(),
+            amount: 10,
+        },
+        HandEvent::Ante {
+            player: "carol".into(),
+            amount: 10,
+        },
+        HandEvent::SmallBlind {
+            player: "bob".into(),
+            amount: 50,
+        },
+        HandEvent::BigBlind {
+            player: "carol".into(),
+            amount: 100,
+        },
+        HandEvent::Straddle {
+            player: "alice".into(),
+            amount: 200,
+        },
+        HandEvent::Raise {
+            player: "bob".into(),
+            to: 600,
+            is_all_in: false,
+        },
+        HandEvent::Fold {
+            player: "carol".into(),
+        },
+        HandEvent::Fold {
+            player: "alice".into(),
+        },
+        HandEvent::Win {
+            player: "bob".into(),
+            amount: 930,
+            pot: 0,
+        },
+    ];
+
+    let expected = "\
+PokerStars Hand #7: Hold'em No Limit (50/100) - 2023/11/14 22:13:20 UTC
+Table 'Chain Poker' 9-max Seat #1 is the button
+Seat 1: alice (5000 in chips)
+Seat 2: bob (5000 in chips)
+Seat 3: carol (5000 in chips)
+alice: posts the ante 10
+bob: posts the ante 10
+carol: posts the ante 10
+bob: posts small blind 50
+carol: posts big blind 100
+alice: posts straddle 200
+*** HOLE CARDS ***
+bob: raises 400 to 600
+carol: folds
+alice: folds
+bob collected 930 from pot
+*** SUMMARY ***
+Total pot 930 | Rake 0";
+
+    assert_eq!(format_hand(7, &events), expected);
+}
@@ -0,0 +1,261 @@
+mod common;
+
+use chain_poker::{
+    error::ContractError,
+    msg::{Ante, ExecuteMsg, InstantiateMsg},
+    state::HandEvent,
+};
+use common::{lobby, Table};
+use cosmwasm_std::Uint128;
+
+fn wins(table: &Table, hand_id: u64) -> Vec<(String, u128, u8)> {
+    table
+        .events(hand_id)
+        .into_iter()
+        .filter_map(|event| match event {
+            HandEvent::Win {
+                player,
+                amount,
+                pot,
+            } => Some((player, amount, pot)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn all_in_players_only_win_what_they_matched() {
+    let mut table = Table::start(
+        lobby(),
+        &[("alice", 1000), ("bobby", 3000), ("carol", 5000)],
+    );
+    // 8c Qs 7d 4h 2s
+    table.set_cards(
+        &[
+            ("alice", &[0, 13]),
+            ("bobby", &[12, 25]),
+            ("carol", &[23, 9]),
+        ],
+        [33, 50, 19, 3, 40],
+    );
+
+    // Alice is on the button, and acts first before the flop.
+    table.execute("alice", ExecuteMsg::AllIn {}).unwrap();
+    table.execute("bobby", ExecuteMsg::AllIn {}).unwrap();
+    table.execute("carol", ExecuteMsg::Call {}).unwrap();
+
+    // Alice's aces win the main pot everyone matched, and bobby's kings win the side pot he
+    // matched with carol.
+    assert_eq!(
+        wins(&table, 1),
+        vec![
+            ("alice".to_string(), 3000, 0),
+            ("bobby".to_string(), 4000, 1),
+        ]
+    );
+
+    // Each stack is recorded at the start of the next hand, before its blinds are posted.
+    let Some(HandEvent::Start { players, .. }) = table.events(2).into_iter().next() else {
+        panic!("the next hand was not dealt");
+    };
+    assert_eq!(
+        players,
+        vec![
+            (0, "alice".to_string(), 3000),
+            (1, "bobby".to_string(), 4000),
+            (2, "carol".to_string(), 2000),
+        ]
+    );
+}
+
+#[test]
+fn antes_are_added_to_the_pot() {
+    let msg = InstantiateMsg {
+        ante: Some(Ante::EveryPlayer(10)),
+        ..lobby()
+    };
+    let mut table = Table::start(msg, &[("alice", 5000), ("bobby", 5000), ("carol", 5000)]);
+
+    table.execute("alice", ExecuteMsg::Fold {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Fold {}).unwrap();
+
+    // Carol wins the three antes along with both blinds.
+    assert_eq!(wins(&table, 1), vec![("carol".to_string(), 180, 0)]);
+}
+
+#[test]
+fn big_blind_ante_is_dead_money_in_the_main_pot() {
+    let msg = InstantiateMsg {
+        ante: Some(Ante::BigBlind),
+        ..lobby()
+    };
+    let mut table = Table::start(msg, &[("alice", 5000), ("bobby", 5000), ("carol", 200)]);
+    // 8c Qs 7d 4h 2s
+    table.set_cards(
+        &[
+            ("alice", &[12, 25]),
+            ("bobby", &[23, 9]),
+            ("carol", &[0, 13]),
+        ],
+        [33, 50, 19, 3, 40],
+    );
+
+    // Carol is all in after posting the big blind and its ante.
+    table
+        .execute(
+            "alice",
+            ExecuteMsg::Raise {
+                to: Uint128::new(300),
+            },
+        )
+        .unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    while table.hand_id() == 1 {
+        let player = table.current_turn();
+        table.execute(&player, ExecuteMsg::Check {}).unwrap();
+    }
+
+    // The ante doesn't count towards carol's share of each pot, but is won with the main pot.
+    assert_eq!(
+        wins(&table, 1),
+        vec![("carol".to_string(), 400, 0), ("alice".to_string(), 400, 1),]
+    );
+}
+
+#[test]
+fn straddle_is_a_third_blind() {
+    let msg = InstantiateMsg {
+        allow_straddle: true,
+        ..lobby()
+    };
+    let mut table = Table::new(msg);
+    for player in ["alice", "bobby", "carol", "david"] {
+        table.buy_in(player, 5000).unwrap();
+    }
+    table
+        .execute("david", ExecuteMsg::SetStraddle { straddle: true })
+        .unwrap();
+    table.execute("alice", ExecuteMsg::StartGame {}).unwrap();
+
+    // David is under the gun, so he straddles and the action starts with alice on the button.
+    assert!(table.events(1).iter().any(|event| matches!(
+        event,
+        HandEvent::Straddle { player, amount: 200 } if player == "david"
+    )));
+    assert_eq!(table.current_turn(), "alice");
+    assert_eq!(
+        table
+            .execute(
+                "alice",
+                ExecuteMsg::Raise {
+                    to: Uint128::new(300),
+                },
+            )
+            .unwrap_err(),
+        ContractError::BetBelowMinimum { min_raise_to: 400 }
+    );
+
+    for player in ["alice", "bobby", "carol"] {
+        table.execute(player, ExecuteMsg::Fold {}).unwrap();
+    }
+    assert_eq!(wins(&table, 1), vec![("david".to_string(), 350, 0)]);
+}
+
+#[test]
+fn last_player_holding_cards_wins_the_pot_straight_away() {
+    let mut table = Table::start(
+        lobby(),
+        &[("alice", 5000), ("bobby", 5000), ("carol", 5000)],
+    );
+
+    table.execute("alice", ExecuteMsg::Fold {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Fold {}).unwrap();
+
+    // Carol wins the blinds without the board being dealt or her hand being shown, and the
+    // next hand is dealt.
+    assert_eq!(wins(&table, 1), vec![("carol".to_string(), 150, 0)]);
+    assert!(!table
+        .events(1)
+        .iter()
+        .any(|event| matches!(event, HandEvent::Board { .. } | HandEvent::Show { .. })));
+    assert_eq!(table.hand_id(), 2);
+}
+
+#[test]
+fn uncontested_winners_can_choose_to_show() {
+    let mut table = Table::start(
+        lobby(),
+        &[("alice", 5000), ("bobby", 5000), ("carol", 5000)],
+    );
+    table
+        .execute("carol", ExecuteMsg::SetShowUncontested { show: true })
+        .unwrap();
+
+    // Everyone sees the flop, then folds to carol's bet.
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    table.execute("carol", ExecuteMsg::Check {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Check {}).unwrap();
+    table
+        .execute(
+            "carol",
+            ExecuteMsg::Raise {
+                to: Uint128::new(100),
+            },
+        )
+        .unwrap();
+    table.execute("alice", ExecuteMsg::Fold {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Fold {}).unwrap();
+
+    let events = table.events(1);
+    let boards = events
+        .iter()
+        .filter(|event| matches!(event, HandEvent::Board { .. }))
+        .count();
+    assert_eq!(boards, 1);
+    assert!(events
+        .iter()
+        .any(|event| matches!(event, HandEvent::Show { player, .. } if player == "carol")));
+    assert_eq!(wins(&table, 1), vec![("carol".to_string(), 400, 0)]);
+}
+
+#[test]
+fn forced_bets_are_only_recorded_when_chips_are_taken() {
+    let msg = InstantiateMsg {
+        ante: Some(Ante::EveryPlayer(100)),
+        allow_straddle: true,
+        ..lobby()
+    };
+    let mut table = Table::new(msg);
+    for (player, stack) in [
+        ("alice", 5000),
+        ("bobby", 5000),
+        ("carol", 100),
+        ("david", 100),
+    ] {
+        table.buy_in(player, stack).unwrap();
+    }
+    table
+        .execute("david", ExecuteMsg::SetStraddle { straddle: true })
+        .unwrap();
+    table.execute("alice", ExecuteMsg::StartGame {}).unwrap();
+
+    // Carol and david are all in after their antes, so post neither the big blind nor the
+    // straddle.
+    let events = table.events(1);
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, HandEvent::Ante { amount: 100, .. }))
+            .count(),
+        4
+    );
+    assert!(events.iter().any(|event| matches!(
+        event,
+        HandEvent::SmallBlind { player, amount: 50 } if player == "bobby"
+    )));
+    assert!(!events.iter().any(|event| matches!(
+        event,
+        HandEvent::BigBlind { .. } | HandEvent::Straddle { .. }
+    )));
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use chain_poker::{
+    msg::{ExecuteMsg, GameVariant},
+    poker::{evaluate_hand, u8_to_card},
+    state::HandEvent,
+};
+use common::{lobby, Table};
+use poker_eval::{Card, Evaluator};
+
+fn cards(cards: &[u8]) -> Vec<Card> {
+    cards.iter().copied().map(u8_to_card).collect()
+}
+
+#[test]
+fn omaha_hands_use_exactly_two_hole_cards() {
+    let evaluator = Evaluator::new();
+    // 2h 3h 4c 5d 9s
+    let table = cards(&[1, 2, 29, 17, 47]);
+
+    // Ah Kh make a wheel with four cards from the board in Hold'em.
+    let holdem = evaluate_hand(&evaluator, GameVariant::Holdem, &cards(&[0, 12]), &table);
+    assert!(holdem.unwrap().eval().is_straight());
+
+    // Ah Kh Qh Jh cannot make the wheel or a flush with only three cards from the board.
+    let omaha = evaluate_hand(
+        &evaluator,
+        GameVariant::Omaha,
+        &cards(&[0, 12, 11, 10]),
+        &table,
+    );
+    assert!(omaha.unwrap().eval().is_high_card());
+}
+
+#[test]
+fn omaha_hands_use_the_best_pair_of_hole_cards() {
+    let evaluator = Evaluator::new();
+    // Ac As Kd 2c 3s
+    let table = cards(&[26, 39, 25, 27, 41]);
+
+    // Ah Ad 7c 8c makes four of a kind with the two aces.
+    let omaha = evaluate_hand(
+        &evaluator,
+        GameVariant::Omaha,
+        &cards(&[0, 13, 32, 33]),
+        &table,
+    );
+    assert!(omaha.unwrap().eval().is_four_of_a_kind());
+}
+
+#[test]
+fn short_deck_flush_beats_full_house() {
+    let evaluator = Evaluator::new();
+    // Kh Kd Kc 9h 6h
+    let table = cards(&[12, 25, 38, 8, 5]);
+
+    // Th 7h make a flush, while 9s 9d make a full house.
+    let flush = cards(&[9, 6]);
+    let full_house = cards(&[47, 21]);
+    let holdem_flush = evaluate_hand(&evaluator, GameVariant::Holdem, &flush, &table);
+    let holdem_full_house = evaluate_hand(&evaluator, GameVariant::Holdem, &full_house, &table);
+    assert!(holdem_full_house > holdem_flush);
+
+    let short_deck_flush = evaluate_hand(&evaluator, GameVariant::ShortDeck, &flush, &table);
+    let short_deck_full_house =
+        evaluate_hand(&evaluator, GameVariant::ShortDeck, &full_house, &table);
+    assert!(short_deck_flush > short_deck_full_house);
+}
+
+#[test]
+fn short_deck_ace_to_nine_is_the_lowest_straight() {
+    let evaluator = Evaluator::new();
+    // 6h 7d 8c Js Qs
+    let table = cards(&[5, 19, 33, 49, 50]);
+
+    let ace_to_nine = evaluate_hand(&evaluator, GameVariant::ShortDeck, &cards(&[0, 47]), &table);
+    let six_to_ten = evaluate_hand(&evaluator, GameVariant::ShortDeck, &cards(&[8, 22]), &table);
+    let trips = evaluate_hand(
+        &evaluator,
+        GameVariant::ShortDeck,
+        &cards(&[18, 44]),
+        &table,
+    );
+    assert!(ace_to_nine.unwrap().eval().is_straight());
+    assert!(ace_to_nine > trips);
+    assert!(six_to_ten > ace_to_nine);
+}
+
+#[test]
+fn hands_are_named_for_the_showdown() {
+    let evaluator = Evaluator::new();
+    // 6h 7d 8c Js Qs
+    let table = cards(&[5, 19, 33, 49, 50]);
+
+    let ace_to_nine = evaluate_hand(&evaluator, GameVariant::ShortDeck, &cards(&[0, 47]), &table);
+    assert_eq!(ace_to_nine.unwrap().name(), "Straight, nine-high");
+
+    let two_pair = evaluate_hand(&evaluator, GameVariant::Holdem, &cards(&[10, 24]), &table);
+    assert_eq!(two_pair.unwrap().name(), "Two pair, queens and jacks");
+}
+
+/// Play a heads up hand to showdown with the given hole cards, checking every street, and return
+/// the chips won by each player.
+fn showdown(alice: &[u8], bobby: &[u8]) -> Vec<(String, u128)> {
+    let mut table = Table::start(lobby(), &[("alice", 5000), ("bobby", 5000)]);
+    // 8c Qs 7d 4h 2s
+    table.set_cards(&[("alice", alice), ("bobby", bobby)], [33, 50, 19, 3, 40]);
+
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+    while table.hand_id() == 1 {
+        let player = table.current_turn();
+        table.execute(&player, ExecuteMsg::Check {}).unwrap();
+    }
+
+    table
+        .events(1)
+        .into_iter()
+        .filter_map(|event| match event {
+            HandEvent::Win { player, amount, .. } => Some((player, amount)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn kickers_break_ties_between_pairs() {
+    // Ah Qd and Kh Qh both pair the queen on the board, and the ace kicker plays.
+    assert_eq!(
+        showdown(&[0, 24], &[12, 11]),
+        vec![("alice".to_string(), 200)]
+    );
+}
+
+#[test]
+fn equal_hands_split_the_pot() {
+    // Qd 3h and Qh 3d make the same pair of queens with the board's kickers.
+    assert_eq!(
+        showdown(&[24, 2], &[11, 15]),
+        vec![("alice".to_string(), 100), ("bobby".to_string(), 100)]
+    );
+}
@@ -0,0 +1,53 @@
+mod common;
+
+use chain_poker::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg},
+    state::HandEvent,
+};
+use common::{lobby, Table};
+
+#[test]
+fn spectators_see_only_public_information() {
+    let mut table = Table::new(lobby());
+    for player in ["alice", "bobby"] {
+        table.buy_in(player, 5000).unwrap();
+    }
+
+    assert_eq!(
+        table.spectator_state().unwrap_err(),
+        ContractError::GameNotStarted
+    );
+
+    table.execute("alice", ExecuteMsg::StartGame {}).unwrap();
+    let state = table.spectator_state().unwrap();
+    assert!(state.table.is_empty());
+    assert_eq!(state.pot, 150);
+    assert_eq!(state.last_action, None);
+
+    // Heads up, the button posts the small blind and acts first before the flop.
+    assert_eq!(state.current_turn, "alice");
+    assert_eq!(state.button_player, "alice");
+    table.execute("alice", ExecuteMsg::Call {}).unwrap();
+
+    let state = table.spectator_state().unwrap();
+    assert_eq!(state.pot, 200);
+    assert_eq!(state.current_turn, "bobby");
+    assert!(matches!(
+        state.last_action,
+        Some(HandEvent::Call { ref player, amount: 50, is_all_in: false }) if player == "alice"
+    ));
+}
+
+#[test]
+fn lobbies_can_disable_spectators() {
+    let table = Table::new(InstantiateMsg {
+        disable_spectators: true,
+        ..lobby()
+    });
+
+    assert_eq!(
+        table.spectator_state().unwrap_err(),
+        ContractError::SpectatorsDisabled
+    );
+}
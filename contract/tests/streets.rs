@@ -0,0 +1,73 @@
+mod common;
+
+use chain_poker::{
+    msg::ExecuteMsg,
+    state::{BETS, CURRENT_MIN_BET, HAS_ACTED},
+};
+use common::{lobby, Table, PERMIT_HOLDER};
+use cosmwasm_std::{Api, Uint128};
+
+fn raise(to: u128) -> ExecuteMsg {
+    ExecuteMsg::Raise {
+        to: Uint128::new(to),
+    }
+}
+
+/// Alice on the button, bobby in the small blind, and carol, who holds the permit, in the big
+/// blind.
+fn start() -> Table {
+    let mut table = Table::new(lobby());
+    table.buy_in("alice", 5000).unwrap();
+    table.buy_in("bobby", 5000).unwrap();
+    table.buy_in_as(PERMIT_HOLDER, "carol", 5000).unwrap();
+    table.execute("alice", ExecuteMsg::StartGame {}).unwrap();
+    table
+}
+
+#[test]
+fn bets_are_reset_for_each_street() {
+    let mut table = start();
+    table.execute("alice", raise(300)).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    table.execute(PERMIT_HOLDER, ExecuteMsg::Call {}).unwrap();
+
+    // The flop starts with no bets, and nobody has acted.
+    assert_eq!(table.game_state().table.len(), 3);
+    assert_eq!(CURRENT_MIN_BET.load(&table.deps.storage).unwrap(), 0);
+    for player in ["alice", "bobby", PERMIT_HOLDER] {
+        let address = table.deps.api.addr_canonicalize(player).unwrap();
+        assert!(!BETS.contains(&table.deps.storage, &address));
+        assert!(!HAS_ACTED.contains(&table.deps.storage, &address));
+    }
+    assert_eq!(table.game_state().pot, 900);
+}
+
+#[test]
+fn the_call_amount_is_for_the_current_street() {
+    let mut table = start();
+
+    // Before the flop, carol has the big blind in and faces alice's raise.
+    table.execute("alice", raise(300)).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    let state = table.game_state();
+    assert_eq!(
+        (state.min_bet, state.bet, state.min_raise_to),
+        (200, 100, 500)
+    );
+    table.execute(PERMIT_HOLDER, ExecuteMsg::Call {}).unwrap();
+    assert_eq!(table.game_state().min_bet, 0);
+
+    // On the flop, only bobby's bet needs to be called.
+    table.execute("bobby", raise(100)).unwrap();
+    let state = table.game_state();
+    assert_eq!(
+        (state.min_bet, state.bet, state.min_raise_to),
+        (100, 0, 200)
+    );
+    table.execute(PERMIT_HOLDER, raise(300)).unwrap();
+
+    // Alice has not acted on the flop yet, so may raise.
+    table.execute("alice", raise(600)).unwrap();
+    table.execute("bobby", ExecuteMsg::Call {}).unwrap();
+    assert_eq!(table.game_state().min_bet, 300);
+}
@@ -0,0 +1,85 @@
+mod common;
+
+use chain_poker::{
+    error::ContractError,
+    msg::{ExecuteMsg, InstantiateMsg},
+    state::HandEvent,
+};
+use common::{lobby, Table};
+
+const TURN_TIME_LIMIT: u64 = 60;
+
+fn start() -> Table {
+    let msg = InstantiateMsg {
+        turn_time_limit: Some(TURN_TIME_LIMIT),
+        ..lobby()
+    };
+    Table::start(msg, &[("alice", 5000), ("bobby", 5000), ("carol", 5000)])
+}
+
+fn claim_timeout(table: &mut Table) -> Result<(), ContractError> {
+    table
+        .execute_later(TURN_TIME_LIMIT, "bobby", ExecuteMsg::ClaimTimeout {})
+        .map(|_| ())
+}
+
+#[test]
+fn timeouts_cannot_be_claimed_before_the_deadline() {
+    let mut table = start();
+
+    assert_eq!(
+        table
+            .execute_later(20, "bobby", ExecuteMsg::ClaimTimeout {})
+            .unwrap_err(),
+        ContractError::TurnNotTimedOut { seconds_left: 40 }
+    );
+    assert_eq!(table.current_turn(), "alice");
+}
+
+#[test]
+fn timed_out_players_fold_and_the_turn_moves_on() {
+    let mut table = start();
+
+    // Alice is first to act, facing the big blind.
+    claim_timeout(&mut table).unwrap();
+    assert!(table
+        .events(1)
+        .iter()
+        .any(|event| matches!(event, HandEvent::Fold { player } if player == "alice")));
+    assert_eq!(table.current_turn(), "bobby");
+    assert!(!table.is_sitting_out("alice"));
+}
+
+#[test]
+fn players_are_sat_out_after_timing_out_twice_in_a_row() {
+    let mut table = start();
+    claim_timeout(&mut table).unwrap();
+    table.execute("bobby", ExecuteMsg::Fold {}).unwrap();
+
+    // In the next hand alice is in the big blind, so times out by checking.
+    table.execute("bobby", ExecuteMsg::Fold {}).unwrap();
+    table.execute("carol", ExecuteMsg::Call {}).unwrap();
+    claim_timeout(&mut table).unwrap();
+    assert!(table
+        .events(2)
+        .iter()
+        .any(|event| matches!(event, HandEvent::Check { player } if player == "alice")));
+    assert!(table.is_sitting_out("alice"));
+}
+
+#[test]
+fn acting_resets_the_timeout_count() {
+    let mut table = start();
+    claim_timeout(&mut table).unwrap();
+    table.execute("bobby", ExecuteMsg::Fold {}).unwrap();
+
+    table.execute("bobby", ExecuteMsg::Fold {}).unwrap();
+    table.execute("carol", ExecuteMsg::Call {}).unwrap();
+    table.execute("alice", ExecuteMsg::Check {}).unwrap();
+
+    // Carol acts first after the flop, then alice times out for the second time, but not in a
+    // row.
+    table.execute("carol", ExecuteMsg::Check {}).unwrap();
+    claim_timeout(&mut table).unwrap();
+    assert!(!table.is_sitting_out("alice"));
+}
@@ -0,0 +1,103 @@
+mod common;
+
+use chain_poker::{
+    error::ContractError,
+    msg::{BlindLevelDuration, ExecuteMsg, InstantiateMsg, TournamentConfig},
+    poker::tournament::validate_config,
+};
+use common::{lobby, Table};
+
+fn tournament() -> TournamentConfig {
+    TournamentConfig {
+        buy_in: 1_000_000u128.into(),
+        starting_stack: 1500,
+        blind_levels: vec![20, 40, 60, 100],
+        level_duration: BlindLevelDuration::Hands(10),
+        payouts: vec![65, 35],
+    }
+}
+
+#[test]
+fn valid_tournament_is_accepted() {
+    assert_eq!(validate_config(&tournament()), Ok(()));
+}
+
+#[test]
+fn blind_schedule_must_be_playable() {
+    let no_levels = TournamentConfig {
+        blind_levels: vec![],
+        ..tournament()
+    };
+    let free_level = TournamentConfig {
+        blind_levels: vec![20, 0],
+        ..tournament()
+    };
+    let instant_levels = TournamentConfig {
+        level_duration: BlindLevelDuration::Seconds(0),
+        ..tournament()
+    };
+
+    for config in [no_levels, free_level, instant_levels] {
+        assert_eq!(
+            validate_config(&config),
+            Err(ContractError::InvalidBlindSchedule)
+        );
+    }
+}
+
+#[test]
+fn starting_stack_must_cover_the_first_big_blind() {
+    let config = TournamentConfig {
+        starting_stack: 10,
+        ..tournament()
+    };
+    assert_eq!(
+        validate_config(&config),
+        Err(ContractError::InvalidStartingStack)
+    );
+}
+
+#[test]
+fn payouts_must_share_the_whole_prize_pool() {
+    for payouts in [vec![], vec![50, 30], vec![60, 50], vec![10; 10]] {
+        let config = TournamentConfig {
+            payouts,
+            ..tournament()
+        };
+        assert_eq!(validate_config(&config), Err(ContractError::InvalidPayouts));
+    }
+}
+
+#[test]
+fn players_keep_their_seat_when_registering_again() {
+    let mut table = Table::new(InstantiateMsg {
+        tournament: Some(tournament()),
+        ..lobby()
+    });
+    for player in ["alice", "bobby", "carol"] {
+        table.buy_in(player, 1_000_000).unwrap();
+    }
+
+    table.execute("alice", ExecuteMsg::Withdraw {}).unwrap();
+    table.execute("bobby", ExecuteMsg::Withdraw {}).unwrap();
+    table.buy_in("bobby", 1_000_000).unwrap();
+
+    let state = table.pre_start_state();
+    assert_eq!(state.occupied_seats, 2);
+    assert_eq!(
+        state.balances,
+        vec![
+            ("alice".to_string(), 0),
+            ("bobby".to_string(), 1500),
+            ("carol".to_string(), 1500)
+        ]
+    );
+    assert_eq!(state.tournament.unwrap().prize_pool, 2_000_000);
+
+    // Bobby is only dealt into one seat.
+    table.execute("alice", ExecuteMsg::StartGame {}).unwrap();
+    assert_eq!(
+        table.pre_start_state().players_in_hand,
+        vec!["bobby", "carol"]
+    );
+}
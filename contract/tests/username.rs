@@ -0,0 +1,49 @@
+mod common;
+
+use chain_poker::{error::ContractError, state::is_valid_username};
+use common::{lobby, Table};
+
+#[test]
+fn usernames_must_be_three_to_fifteen_characters() {
+    assert!(!is_valid_username("ab"));
+    assert!(is_valid_username("abc"));
+    assert!(is_valid_username("fifteen_letters"));
+    assert!(!is_valid_username("sixteen_letters_"));
+}
+
+#[test]
+fn usernames_may_only_hold_letters_digits_underscores_and_hyphens() {
+    assert!(is_valid_username("Card-Shark_99"));
+    for username in ["two words", "semi;colon", "émile", "<script>"] {
+        assert!(!is_valid_username(username), "{username}");
+    }
+}
+
+#[test]
+fn invalid_usernames_cannot_buy_in() {
+    let mut table = Table::new(lobby());
+
+    assert_eq!(
+        table.buy_in_as("alice", "al", 5000).unwrap_err(),
+        ContractError::InvalidUsername
+    );
+    assert_eq!(
+        table.buy_in_as("alice", "alice smith", 5000).unwrap_err(),
+        ContractError::InvalidUsername
+    );
+    assert!(table.pre_start_state().balances.is_empty());
+}
+
+#[test]
+fn usernames_are_unique_regardless_of_case() {
+    let mut table = Table::new(lobby());
+    table.buy_in_as("alice", "Shark", 5000).unwrap();
+
+    assert_eq!(
+        table.buy_in_as("bobby", "SHARK", 5000).unwrap_err(),
+        ContractError::UsernameTaken {
+            username: "SHARK".to_string()
+        }
+    );
+    table.buy_in_as("bobby", "minnow", 5000).unwrap();
+}
@@ -33,6 +33,8 @@ function BuyIn({
     required: true,
     maxLength: 15,
     minLength: 3,
+    pattern: /^[A-Za-z0-9_-]*$/,
+    patternMessage: "Only letters, numbers, underscores and hyphens are allowed",
   });
 
   async function handleBuyIn() {
@@ -21,6 +21,8 @@ function CreateLobby({ backAction, networkClient }: CreateLobbyProps): VNode {
     required: true,
     minLength: 3,
     maxLength: 15,
+    pattern: /^[A-Za-z0-9_-]*$/,
+    patternMessage: "Only letters, numbers, underscores and hyphens are allowed",
   });
   const [bigBlind, setBigBlind] = useScrtValidation({
     minValueUscrt: 2n,
@@ -1,10 +1,17 @@
 import { Box, Button } from "@mui/material";
 import type { VNode } from "preact";
 import { useLocation } from "preact-iso";
-import type { SecretNetworkClient } from "secretjs";
-import { Result } from "typescript-result";
+import type { SecretNetworkClient, TxResponse } from "secretjs";
+import { type AsyncResult, Result } from "typescript-result";
 
-import { placeBet, withdraw } from "../secretnetwork/chainPokerContract";
+import {
+  allIn,
+  call,
+  check,
+  fold,
+  raise,
+  withdraw,
+} from "../secretnetwork/chainPokerContract";
 import type { GameState } from "../secretnetwork/types";
 import { uScrtToScrt } from "../secretnetwork/utils";
 import CardSet from "./CardSet";
@@ -27,6 +34,8 @@ function Game({
   networkClient,
   lobbyCode,
   min_bet,
+  bet,
+  min_raise_to,
 }: GameProps): VNode | undefined {
   const location = useLocation();
 
@@ -39,8 +48,17 @@ function Game({
     ([username]) => username === playersUsername,
   )![1];
 
-  function sendBet(betAmount: bigint): void {
-    Result.fromAsync(placeBet(betAmount, lobbyCode, networkClient)).onFailure(
+  function sendAction(
+    action: (
+      lobbyCode: string,
+      networkClient: SecretNetworkClient,
+    ) => AsyncResult<TxResponse, Error>,
+  ): void {
+    Result.fromAsync(action(lobbyCode, networkClient)).onFailure(console.error);
+  }
+
+  function sendRaise(to: bigint): void {
+    Result.fromAsync(raise(to, lobbyCode, networkClient)).onFailure(
       console.error,
     );
   }
@@ -78,8 +96,14 @@ function Game({
         cards={hand}
         chipBalance={BigInt(chipBalance)}
         minBet={BigInt(min_bet)}
+        streetBet={BigInt(bet)}
+        minRaiseTo={BigInt(min_raise_to)}
         ourTurn={current_turn === playersUsername}
-        onBet={sendBet}
+        onFold={() => sendAction(fold)}
+        onCheck={() => sendAction(check)}
+        onCall={() => sendAction(call)}
+        onRaise={sendRaise}
+        onAllIn={() => sendAction(allIn)}
       />
     </Box>
   );
@@ -10,15 +10,35 @@ interface HandProps {
   cards: number[] | null;
   chipBalance: bigint;
   minBet: bigint;
+  streetBet: bigint;
+  minRaiseTo: bigint;
   ourTurn: boolean;
-  onBet: (betAmount: bigint) => void;
+  onFold: () => void;
+  onCheck: () => void;
+  onCall: () => void;
+  onRaise: (to: bigint) => void;
+  onAllIn: () => void;
 }
 
-function Hand({ cards, chipBalance, minBet, ourTurn, onBet }: HandProps) {
-  const [betAmount, setBetAmount] = useScrtValidation({
-    minValueUscrt: minBet,
-    maxValueUscrt: chipBalance,
-    allowZero: true,
+function Hand({
+  cards,
+  chipBalance,
+  minBet,
+  streetBet,
+  minRaiseTo,
+  ourTurn,
+  onFold,
+  onCheck,
+  onCall,
+  onRaise,
+  onAllIn,
+}: HandProps) {
+  // Raises are to a street total, so the most a player can raise to includes what they have
+  // already bet on this street.
+  const maxRaiseTo = chipBalance + streetBet;
+  const [raiseAmount, setRaiseAmount] = useScrtValidation({
+    minValueUscrt: minRaiseTo < maxRaiseTo ? minRaiseTo : maxRaiseTo,
+    maxValueUscrt: maxRaiseTo,
   });
 
   return (
@@ -44,7 +64,7 @@ function Hand({ cards, chipBalance, minBet, ourTurn, onBet }: HandProps) {
           left: "2em",
         }}
       />
-      <CardSet cards={cards} maxCards={2} />
+      <CardSet cards={cards} maxCards={cards?.length ?? 2} />
       {ourTurn && (
         <Box
           sx={{ position: "fixed", right: "2em" }}
@@ -52,18 +72,33 @@ function Hand({ cards, chipBalance, minBet, ourTurn, onBet }: HandProps) {
           columnGap="1em"
           justifyContent="center"
         >
+          <Button onClick={onFold} variant="outlined" color="error">
+            Fold
+          </Button>
+          {minBet === 0n ? (
+            <Button onClick={onCheck} variant="outlined" color="success">
+              Check
+            </Button>
+          ) : (
+            <Button onClick={onCall} variant="outlined" color="success">
+              Call
+            </Button>
+          )}
           <ScrtInput
-            state={betAmount}
-            setState={setBetAmount}
+            state={raiseAmount}
+            setState={setRaiseAmount}
             color="success"
           />
           <Button
-            onClick={() => onBet(betAmount.uScrt!)}
-            disabled={betAmount.error !== null}
+            onClick={() => onRaise(raiseAmount.uScrt!)}
+            disabled={raiseAmount.error !== null}
             variant="outlined"
             color="success"
           >
-            Place Bet
+            Raise to
+          </Button>
+          <Button onClick={onAllIn} variant="outlined" color="success">
+            All in
           </Button>
         </Box>
       )}
@@ -9,6 +9,8 @@ interface StringValidationRules {
   required?: boolean;
   maxLength?: number;
   minLength?: number;
+  pattern?: RegExp;
+  patternMessage?: string;
 }
 
 interface ValidationState {
@@ -38,6 +40,8 @@ function useStringValidation(
       value.length < rules.minLength
     ) {
       errorMessage = `This field must be at least ${rules.minLength} characters long`;
+    } else if (rules.pattern !== undefined && !rules.pattern.test(value)) {
+      errorMessage = rules.patternMessage ?? "This field is invalid";
     }
 
     setError(errorMessage);
@@ -2,7 +2,12 @@ import type { Permit, SecretNetworkClient, TxResponse } from "secretjs";
 import * as secretts from "secretts";
 import { type AsyncResult, Result } from "typescript-result";
 
-import type { GameState, PreStartState } from "./types";
+import type {
+  GameState,
+  HandHistoryResponse,
+  PreStartState,
+  VerifyHandResponse,
+} from "./types";
 
 const SECRET_CHAIN_ID = import.meta.env.VITE_SECRET_CHAIN_ID;
 const CONTRACT_CODE_HASH = import.meta.env.VITE_CONTRACT_CODE_HASH;
@@ -88,28 +93,146 @@ function startGame(
 }
 
 /**
- * Place a bet on the current hand.
+ * Send a betting action for the current hand.
  *
- * @param amount - The number of uSCRT to bet, 0 for check/fold.
+ * @param action - The execute message for the action to take.
  * @param lobbyCode - The address of the instantiated contract.
  * @param networkClient - A Secret Network client initialised with Keplr.
  *
  * @returns A result containing the transaction result from the contract if
  *    successful, otherwise an error.
  */
-function placeBet(
-  amount: bigint,
+function sendAction(
+  action: object,
   lobbyCode: string,
   networkClient: SecretNetworkClient,
 ): AsyncResult<TxResponse, Error> {
   return secretts.tryExecute(
-    { place_bet: { value: amount.toString() } },
+    action,
     50_000,
     { contractAddress: lobbyCode, contractCodeHash: CONTRACT_CODE_HASH },
     networkClient,
   );
 }
 
+/**
+ * Fold the current hand.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the transaction result from the contract if
+ *    successful, otherwise an error.
+ */
+function fold(
+  lobbyCode: string,
+  networkClient: SecretNetworkClient,
+): AsyncResult<TxResponse, Error> {
+  return sendAction({ fold: {} }, lobbyCode, networkClient);
+}
+
+/**
+ * Check, passing the action on without betting.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the transaction result from the contract if
+ *    successful, otherwise an error.
+ */
+function check(
+  lobbyCode: string,
+  networkClient: SecretNetworkClient,
+): AsyncResult<TxResponse, Error> {
+  return sendAction({ check: {} }, lobbyCode, networkClient);
+}
+
+/**
+ * Call the current bet.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the transaction result from the contract if
+ *    successful, otherwise an error.
+ */
+function call(
+  lobbyCode: string,
+  networkClient: SecretNetworkClient,
+): AsyncResult<TxResponse, Error> {
+  return sendAction({ call: {} }, lobbyCode, networkClient);
+}
+
+/**
+ * Raise the current bet.
+ *
+ * @param to - The total number of uSCRT to have bet this betting round.
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the transaction result from the contract if
+ *    successful, otherwise an error.
+ */
+function raise(
+  to: bigint,
+  lobbyCode: string,
+  networkClient: SecretNetworkClient,
+): AsyncResult<TxResponse, Error> {
+  return sendAction(
+    { raise: { to: to.toString() } },
+    lobbyCode,
+    networkClient,
+  );
+}
+
+/**
+ * Bet all remaining chips on the current hand.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the transaction result from the contract if
+ *    successful, otherwise an error.
+ */
+function allIn(
+  lobbyCode: string,
+  networkClient: SecretNetworkClient,
+): AsyncResult<TxResponse, Error> {
+  return sendAction({ all_in: {} }, lobbyCode, networkClient);
+}
+
+/**
+ * Keep your seat and chips, but stop being dealt into new hands.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the transaction result from the contract if
+ *    successful, otherwise an error.
+ */
+function sitOut(
+  lobbyCode: string,
+  networkClient: SecretNetworkClient,
+): AsyncResult<TxResponse, Error> {
+  return sendAction({ sit_out: {} }, lobbyCode, networkClient);
+}
+
+/**
+ * Start being dealt into new hands again after sitting out.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the transaction result from the contract if
+ *    successful, otherwise an error.
+ */
+function sitIn(
+  lobbyCode: string,
+  networkClient: SecretNetworkClient,
+): AsyncResult<TxResponse, Error> {
+  return sendAction({ sit_in: {} }, lobbyCode, networkClient);
+}
+
 /**
  * Withdraw from the gamw, cashing out all available balance.
  *
@@ -174,6 +297,57 @@ async function viewPreStartState(
   );
 }
 
+/**
+ * Query the contract for a page of the history of a hand.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param handId - The hand to view the history of.
+ * @param page - The page of events to view, starting from 0.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing a page of the hand's history if successful,
+ *    otherwise an error.
+ */
+async function viewHandHistory(
+  lobbyCode: string,
+  handId: number,
+  page: number,
+  networkClient: SecretNetworkClient,
+): Promise<Result<HandHistoryResponse, Error>> {
+  return await Result.fromAsync(getPermit(lobbyCode, networkClient)).map(
+    (permit) =>
+      secretts.queryContract<HandHistoryResponse>(
+        { hand_history: { hand_id: handId, permit, page } },
+        { contractAddress: lobbyCode, contractCodeHash: CONTRACT_CODE_HASH },
+        networkClient,
+      ),
+  );
+}
+
+/**
+ * Query the contract for the deck commitment of a hand. Once the hand is
+ * over, this also returns the commitment to each card, along with the board
+ * and any shown hands and their salts.
+ *
+ * @param lobbyCode - The address of the instantiated contract.
+ * @param handId - The hand to verify.
+ * @param networkClient - A Secret Network client initialised with Keplr.
+ *
+ * @returns A result containing the deck commitment of the hand if
+ *    successful, otherwise an error.
+ */
+async function verifyHand(
+  lobbyCode: string,
+  handId: number,
+  networkClient: SecretNetworkClient,
+): Promise<Result<VerifyHandResponse, Error>> {
+  return await secretts.queryContract<VerifyHandResponse>(
+    { verify_hand: { hand_id: handId } },
+    { contractAddress: lobbyCode, contractCodeHash: CONTRACT_CODE_HASH },
+    networkClient,
+  );
+}
+
 /**
  * Get a permit to perform authenticated queries.
  *
@@ -209,11 +383,19 @@ async function getPermit(
 }
 
 export {
+  allIn,
   buyIn,
+  call,
+  check,
   createLobby,
-  placeBet,
+  fold,
+  raise,
+  sitIn,
+  sitOut,
   startGame,
+  verifyHand,
   viewGameState,
+  viewHandHistory,
   viewPreStartState,
   withdraw,
 };
@@ -2,6 +2,33 @@ interface LobbyConfig {
   big_blind: string;
   max_buy_in_bb: number;
   min_buy_in_bb: number;
+  turn_time_limit: number | null; // seconds
+  tournament: TournamentConfig | null;
+  ante: { every_player: number } | "big_blind" | null;
+  allow_straddle: boolean;
+  betting_structure: BettingStructure;
+  variant: GameVariant;
+  disable_spectators: boolean;
+}
+
+type BettingStructure = "no_limit" | "pot_limit" | { fixed_limit: { raise_cap: number } };
+
+type GameVariant = "holdem" | "omaha" | "short_deck";
+
+interface TournamentConfig {
+  buy_in: string;
+  starting_stack: number;
+  blind_levels: number[];
+  level_duration: { hands: number } | { seconds: number };
+  payouts: number[]; // percentage of the prize pool, from first place down
+}
+
+interface TournamentState {
+  prize_pool: string;
+  blind_level: number;
+  big_blind: number;
+  eliminated: string[];
+  prizes: [string, string][]; // [username, prize]
 }
 
 interface PlayerInfo {
@@ -13,10 +40,82 @@ interface GameState {
   balances: [string, string][]; // [username, balance]
   table: number[]; // cards are 8 bit integers
   pot: string;
-  hand: [number, number] | null;
+  hand_id: number;
+  hand: number[] | null; // two hole cards in Hold'em, four in Omaha
+  current_turn: string;
+  button_player: string;
+  min_bet: string; // the amount needed to call
+  bet: string; // chips already bet on this street
+  min_raise_to: string;
+  sitting_out: string[]; // usernames
+}
+
+type Street = "flop" | "turn" | "river";
+
+// Players are identified by username, amounts are in uSCRT.
+type HandEvent =
+  | {
+      start: {
+        time: number; // seconds since the Unix epoch
+        button_seat: number;
+        big_blind: number;
+        variant: GameVariant;
+        betting_structure: BettingStructure;
+        players: [number, string, string][]; // [seat, username, stack]
+      };
+    }
+  | { ante: { player: string; amount: string } }
+  | { small_blind: { player: string; amount: string } }
+  | { big_blind: { player: string; amount: string } }
+  | { straddle: { player: string; amount: string } }
+  | { fold: { player: string } }
+  | { check: { player: string } }
+  | { call: { player: string; amount: string; is_all_in: boolean } }
+  | { raise: { player: string; to: string; is_all_in: boolean } }
+  | { board: { street: Street; cards: number[] } }
+  | { show: { player: string; hand: number[] } }
+  | { win: { player: string; amount: string; pot: number } };
+
+interface HandHistoryResponse {
+  hand_id: number;
+  events: HandEvent[];
+  total_events: number;
+}
+
+interface RevealedCard {
+  position: number; // position in the deck, in dealing order
+  card: number;
+  salt: string; // base64
+}
+
+interface VerifyHandResponse {
+  hand_id: number;
+  commitment: string; // base64 SHA-256 of every card commitment in dealing order
+  card_commitments: string[] | null; // base64, published once the hand is over
+  revealed_cards: RevealedCard[] | null; // the board and shown hands
+  is_valid: boolean | null;
+}
+
+interface SpectatorState {
+  balances: [string, string][]; // [username, balance]
+  table: number[];
+  pot: string;
+  hand_id: number;
   current_turn: string;
   button_player: string;
-  min_bet: string;
+  last_action: HandEvent | null; // the last fold, check, call or raise
+}
+
+interface ShownHand {
+  player: string; // username
+  hand: number[];
+  hand_name: string | null; // such as "Flush, ace-high"
+}
+
+interface LastShowdown {
+  hand_id: number;
+  table: number[];
+  hands: ShownHand[]; // in seat order
 }
 
 interface PreStartState {
@@ -24,6 +123,22 @@ interface PreStartState {
   lobby_config: LobbyConfig;
   is_started: boolean;
   balances: [string, string][]; // [username, balance]
+  occupied_seats: number;
+  players_in_hand: string[]; // usernames
+  tournament: TournamentState | null;
 }
 
-export type { GameState, LobbyConfig, PlayerInfo, PreStartState };
+export type {
+  GameState,
+  HandEvent,
+  HandHistoryResponse,
+  LastShowdown,
+  LobbyConfig,
+  PlayerInfo,
+  PreStartState,
+  ShownHand,
+  SpectatorState,
+  TournamentConfig,
+  TournamentState,
+  VerifyHandResponse,
+};
@@ -23,7 +23,7 @@ async function main(): Promise<Result<void, Error>> {
   );
 
   const executeMessage = {
-    place_bet: { value: "0" },
+    check: {},
   };
   const gasLimit = 400_000;
 